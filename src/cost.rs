@@ -0,0 +1,350 @@
+use alloc::vec::Vec;
+use core::cmp::{max, min};
+
+use crate::{calculate, calculate_gp3_limits, can_satisfy, eligible_volume_types, min_io_iops_for_throughput, Limit, VolumeLimitError, COST_ORDER, VolumeType, GP3_BASELINE_IOPS, GP3_BASELINE_THROUGHPUT, GP3_IOPS_PER_MIBPS, GP3_MAX_SIZE_GB, GP3_MAX_THROUGHPUT, IO_MIN_IOPS};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CostEstimate {
+    pub storage_usd: f64,
+    pub iops_usd: f64,
+    pub throughput_usd: f64,
+    pub total_usd: f64,
+}
+
+// Monthly prices in USD per GiB, per provisioned IOPS and per provisioned MiB/s.
+struct PriceTable {
+    gp2_storage: f64,
+    gp3_storage: f64,
+    gp3_iops: f64,             // Charged only for IOPS above the 3000 baseline
+    gp3_throughput: f64,       // Charged only for throughput above the 125MiB/s baseline
+    io1_storage: f64,
+    io1_iops: f64,
+    io2_storage: f64,
+    io2_iops: [f64; 3],        // Tiers: up to 32000 IOPS, 32001 to 64000 IOPS, above 64000 IOPS
+    st1_storage: f64,
+    sc1_storage: f64,
+    standard_storage: f64,     // I/O requests are billed separately and not estimated
+}
+
+const US_EAST_1: PriceTable = PriceTable {
+    gp2_storage: 0.10,
+    gp3_storage: 0.08,
+    gp3_iops: 0.005,
+    gp3_throughput: 0.04,
+    io1_storage: 0.125,
+    io1_iops: 0.065,
+    io2_storage: 0.125,
+    io2_iops: [0.065, 0.0455, 0.032],
+    st1_storage: 0.045,
+    sc1_storage: 0.015,
+    standard_storage: 0.05,
+};
+
+fn price_table(region: &str) -> Result<&'static PriceTable, VolumeLimitError> {
+    match region {
+        "us-east-1" => Ok(&US_EAST_1),
+        _ => Err(VolumeLimitError::UnknownRegion),
+    }
+}
+
+fn io2_iops_cost(prices: &PriceTable, iops: u32) -> f64 {
+    let first_tier = min(iops, 32000);
+    let second_tier = min(iops, 64000).saturating_sub(32000);
+    let third_tier = iops.saturating_sub(64000);
+    f64::from(first_tier) * prices.io2_iops[0] + f64::from(second_tier) * prices.io2_iops[1] + f64::from(third_tier) * prices.io2_iops[2]
+}
+
+// Monthly cost of gp3 IOPS and throughput above the free baseline.
+fn gp3_surcharges(prices: &PriceTable, limit: &Limit) -> (f64, f64) {
+    (
+        f64::from(limit.iops.saturating_sub(GP3_BASELINE_IOPS)) * prices.gp3_iops,
+        f64::from(limit.speed.saturating_sub(GP3_BASELINE_THROUGHPUT)) * prices.gp3_throughput,
+    )
+}
+
+/// Monthly cost of provisioning gp3 with `iops` and `throughput` on top of the storage cost,
+/// 0 when both are at the free baseline.
+pub fn gp3_provisioning_surcharge(iops: u32, throughput: u32, region: &str) -> Result<f64, VolumeLimitError> {
+    let prices = price_table(region)?;
+    // The largest size allows every IOPS value, so only the IOPS and throughput rules are checked.
+    let limit = calculate_gp3_limits(GP3_MAX_SIZE_GB, Some(iops), Some(throughput))?;
+    let (iops_usd, throughput_usd) = gp3_surcharges(prices, &limit);
+    Ok(iops_usd + throughput_usd)
+}
+
+/// Monthly cost of a volume, the configuration is validated the same way as `calculate` does.
+pub fn estimate_monthly_cost(volume_type: VolumeType, size_gb: u32, provisioned_iops: Option<u32>, provisioned_throughput: Option<u32>, region: &str) -> Result<CostEstimate, VolumeLimitError> {
+    let prices = price_table(region)?;
+    let limit = calculate(volume_type, size_gb, provisioned_iops, provisioned_throughput)?;
+
+    // Sizes and IOPS are converted to f64 before they're multiplied by a price, so no product is done in u32.
+    let size = f64::from(size_gb);
+    let (storage_usd, iops_usd, throughput_usd) = match volume_type {
+        VolumeType::Gp2 => (size * prices.gp2_storage, 0.0, 0.0),
+        VolumeType::Gp3 => {
+            let (iops_usd, throughput_usd) = gp3_surcharges(prices, &limit);
+            (size * prices.gp3_storage, iops_usd, throughput_usd)
+        }
+        VolumeType::Io1 => (size * prices.io1_storage, f64::from(limit.iops) * prices.io1_iops, 0.0),
+        VolumeType::Io2 => (size * prices.io2_storage, io2_iops_cost(prices, limit.iops), 0.0),
+        VolumeType::St1 => (size * prices.st1_storage, 0.0, 0.0),
+        VolumeType::Sc1 => (size * prices.sc1_storage, 0.0, 0.0),
+        VolumeType::Standard => (size * prices.standard_storage, 0.0, 0.0),
+    };
+    Ok(CostEstimate { storage_usd, iops_usd, throughput_usd, total_usd: storage_usd + iops_usd + throughput_usd })
+}
+
+/// Monthly cost of `volume_count` identical volumes. The count is a u64 and converted to f64 before it's
+/// multiplied, so fleets larger than `u32::MAX` volumes neither overflow nor truncate.
+pub fn estimate_fleet_cost(volume_type: VolumeType, size_gb: u32, provisioned_iops: Option<u32>, provisioned_throughput: Option<u32>, volume_count: u64, region: &str) -> Result<CostEstimate, VolumeLimitError> {
+    let cost = estimate_monthly_cost(volume_type, size_gb, provisioned_iops, provisioned_throughput, region)?;
+    let count = volume_count as f64;            // Exact up to 2^53 volumes
+    Ok(CostEstimate {
+        storage_usd: cost.storage_usd * count,
+        iops_usd: cost.iops_usd * count,
+        throughput_usd: cost.throughput_usd * count,
+        total_usd: cost.total_usd * count,
+    })
+}
+
+// Least IOPS and throughput to provision on `volume_type` so it reaches both targets.
+fn minimal_provisioning(volume_type: VolumeType, target_iops: u32, target_mibps: u32) -> (Option<u32>, Option<u32>) {
+    match volume_type {
+        VolumeType::Gp3 => {
+            let throughput = max(target_mibps, GP3_BASELINE_THROUGHPUT);
+            let iops = max(max(target_iops, GP3_BASELINE_IOPS), throughput.saturating_mul(GP3_IOPS_PER_MIBPS));     // 0.25MiB/s per IOPS at most
+            (
+                if iops > GP3_BASELINE_IOPS { Some(iops) } else { None },
+                if throughput > GP3_BASELINE_THROUGHPUT { Some(throughput) } else { None },
+            )
+        }
+        VolumeType::Io1 | VolumeType::Io2 => {
+            let iops_for_throughput = min_io_iops_for_throughput(target_mibps).unwrap_or(u32::MAX);
+            (Some(max(max(target_iops, IO_MIN_IOPS), iops_for_throughput)), None)
+        }
+        _ => (None, None),
+    }
+}
+
+/// Monthly cost of every volume type able to reach both targets at `size_gb`, cheapest first.
+/// IOPS and throughput are provisioned at the least amount that meets the targets.
+pub fn compare_costs(size_gb: u32, target_iops: u32, target_mibps: u32, region: &str) -> Result<Vec<(VolumeType, f64)>, VolumeLimitError> {
+    price_table(region)?;
+    let mut costs: Vec<(VolumeType, f64)> = eligible_volume_types(target_iops, target_mibps, size_gb).into_iter()
+        .filter_map(|volume_type| {
+            let (iops, throughput) = minimal_provisioning(volume_type, target_iops, target_mibps);
+            estimate_monthly_cost(volume_type, size_gb, iops, throughput, region).ok().map(|cost| (volume_type, cost.total_usd))
+        })
+        .collect();
+    costs.sort_by(|a, b| a.1.total_cmp(&b.1));
+    Ok(costs)
+}
+
+/// Cheapest volume type and size reaching both targets, with provisioning kept at the least amount that meets them.
+/// Every type is tried at the smallest size able to satisfy the targets, since storage cost only grows with size.
+pub fn cheapest_sizing(target_iops: u32, target_mibps: u32, region: &str) -> Result<(VolumeType, u32, CostEstimate), VolumeLimitError> {
+    price_table(region)?;
+    let mut cheapest: Option<(VolumeType, u32, CostEstimate)> = None;
+    for volume_type in COST_ORDER {
+        let Some(size_gb) = (1..=16384).find(|&size_gb| can_satisfy(volume_type, target_iops, target_mibps, size_gb)) else {
+            continue;
+        };
+        let (iops, throughput) = minimal_provisioning(volume_type, target_iops, target_mibps);
+        let Ok(cost) = estimate_monthly_cost(volume_type, size_gb, iops, throughput, region) else {
+            continue;
+        };
+        if cheapest.as_ref().is_none_or(|(_, _, best)| cost.total_usd < best.total_usd) {
+            cheapest = Some((volume_type, size_gb, cost));
+        }
+    }
+    cheapest.ok_or(VolumeLimitError::NoEligibleVolumeType)
+}
+
+/// Suggests gp3 IOPS and throughput that cost no more than the given provisioning, trading IOPS above what
+/// the 0.25:1 throughput to IOPS ratio needs for the highest throughput the budget allows.
+/// Returns `None` when no such trade raises throughput.
+pub fn gp3_rebalance(size_gb: u32, iops: u32, throughput: u32, region: &str) -> Result<Option<(u32, u32)>, VolumeLimitError> {
+    let budget = estimate_monthly_cost(VolumeType::Gp3, size_gb, Some(iops), Some(throughput), region)?.total_usd;
+    let rebalanced = (throughput + 1..=GP3_MAX_THROUGHPUT).rev().find_map(|candidate_throughput| {
+        let candidate_iops = max(GP3_BASELINE_IOPS, candidate_throughput * GP3_IOPS_PER_MIBPS);
+        match estimate_monthly_cost(VolumeType::Gp3, size_gb, Some(candidate_iops), Some(candidate_throughput), region) {
+            Ok(cost) if cost.total_usd <= budget + 1e-9 => Some((candidate_iops, candidate_throughput)),
+            _ => None,
+        }
+    });
+    Ok(rebalanced)
+}
+
+/// Baseline throughput in MiB/s per monthly USD, 0 when the cost isn't positive.
+pub fn throughput_efficiency(limit: &Limit, monthly_cost_usd: f64) -> f64 {
+    per_usd(limit.speed, monthly_cost_usd)
+}
+
+/// Baseline IOPS per monthly USD, 0 when the cost isn't positive.
+pub fn iops_efficiency(limit: &Limit, monthly_cost_usd: f64) -> f64 {
+    per_usd(limit.iops, monthly_cost_usd)
+}
+
+fn per_usd(value: u32, monthly_cost_usd: f64) -> f64 {
+    if monthly_cost_usd > 0.0 { f64::from(value) / monthly_cost_usd } else { 0.0 }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn assert_usd(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() < 1e-9, "expected {} USD, got {} USD", expected, actual);
+    }
+
+    // Sums of many amounts accumulate rounding error proportional to the total.
+    fn assert_usd_relative(actual: f64, expected: f64) {
+        assert!((actual - expected).abs() <= expected.abs() * 1e-12, "expected {} USD, got {} USD", expected, actual);
+    }
+
+    #[test]
+    fn test_gp3_500_6000_iops() {
+        let cost = estimate_monthly_cost(VolumeType::Gp3, 500, Some(6000), None, "us-east-1").unwrap();
+        assert_usd(cost.storage_usd, 40.0);
+        assert_usd(cost.iops_usd, 15.0);
+        assert_usd(cost.throughput_usd, 0.0);
+        assert_usd(cost.total_usd, 55.0);
+    }
+
+    #[test]
+    fn test_gp3_baseline_is_free() {
+        let cost = estimate_monthly_cost(VolumeType::Gp3, 100, None, None, "us-east-1").unwrap();
+        assert_usd(cost.total_usd, 8.0);
+        let cost = estimate_monthly_cost(VolumeType::Gp3, 100, Some(4000), Some(250), "us-east-1").unwrap();
+        assert_usd(cost.throughput_usd, 5.0);
+        assert_usd(cost.total_usd, 18.0);
+    }
+
+    #[test]
+    fn test_gp2_st1_sc1_standard() {
+        assert_usd(estimate_monthly_cost(VolumeType::Gp2, 1000, None, None, "us-east-1").unwrap().total_usd, 100.0);
+        assert_usd(estimate_monthly_cost(VolumeType::St1, 1000, None, None, "us-east-1").unwrap().total_usd, 45.0);
+        assert_usd(estimate_monthly_cost(VolumeType::Sc1, 1000, None, None, "us-east-1").unwrap().total_usd, 15.0);
+        assert_usd(estimate_monthly_cost(VolumeType::Standard, 1000, None, None, "us-east-1").unwrap().total_usd, 50.0);
+    }
+
+    #[test]
+    fn test_io1_io2() {
+        let cost = estimate_monthly_cost(VolumeType::Io1, 1000, Some(10000), None, "us-east-1").unwrap();
+        assert_usd(cost.storage_usd, 125.0);
+        assert_usd(cost.iops_usd, 650.0);
+        let cost = estimate_monthly_cost(VolumeType::Io2, 1000, Some(40000), None, "us-east-1").unwrap();
+        assert_usd(cost.iops_usd, 32000.0 * 0.065 + 8000.0 * 0.0455);
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert_eq!(estimate_monthly_cost(VolumeType::Gp3, 500, None, None, "mars-north-1").unwrap_err(), VolumeLimitError::UnknownRegion);
+        assert!(estimate_monthly_cost(VolumeType::Gp3, 0, None, None, "us-east-1").is_err());
+    }
+
+    #[test]
+    fn test_compare_costs_gp3_cheaper_than_io1() {
+        let costs = compare_costs(1000, 10000, 250, "us-east-1").unwrap();
+        let types: Vec<_> = costs.iter().map(|(volume_type, _)| *volume_type).collect();
+        assert_eq!(types, [VolumeType::Gp3, VolumeType::Io1, VolumeType::Io2]);
+        assert_usd(costs[0].1, 80.0 + 35.0 + 5.0);
+        assert_usd(costs[1].1, 125.0 + 650.0);
+    }
+
+    #[test]
+    fn test_compare_costs_throughput_heavy() {
+        let costs = compare_costs(2000, 0, 40, "us-east-1").unwrap();
+        assert_eq!(costs[0].0, VolumeType::St1);
+        assert!(costs.windows(2).all(|pair| pair[0].1 <= pair[1].1));
+        assert_eq!(compare_costs(2000, 0, 40, "mars-north-1").unwrap_err(), VolumeLimitError::UnknownRegion);
+    }
+
+    #[test]
+    fn test_cheapest_sizing_modest_target() {
+        let (volume_type, size_gb, cost) = cheapest_sizing(5000, 200, "us-east-1").unwrap();
+        assert_eq!(volume_type, VolumeType::Gp3);
+        assert_eq!(size_gb, 10);
+        assert_usd(cost.total_usd, 0.8 + 10.0 + 3.0);
+    }
+
+    #[test]
+    fn test_cheapest_sizing_throughput_only() {
+        let (volume_type, size_gb, cost) = cheapest_sizing(0, 400, "us-east-1").unwrap();
+        assert_eq!(volume_type, VolumeType::Gp3);                   // A 1GiB gp3 volume beats a 10TiB st1 volume
+        assert_eq!(size_gb, 1);
+        assert_usd(cost.total_usd, 0.08 + 275.0 * 0.04);
+        assert_eq!(cheapest_sizing(100000, 0, "us-east-1").unwrap_err(), VolumeLimitError::NoEligibleVolumeType);
+        assert_eq!(cheapest_sizing(5000, 200, "mars-north-1").unwrap_err(), VolumeLimitError::UnknownRegion);
+    }
+
+    #[test]
+    fn test_gp3_rebalance_iops_to_throughput() {
+        let (iops, throughput) = gp3_rebalance(100, 10000, 200, "us-east-1").unwrap().unwrap();
+        assert_eq!((iops, throughput), (3864, 966));
+        let before = estimate_monthly_cost(VolumeType::Gp3, 100, Some(10000), Some(200), "us-east-1").unwrap();
+        let after = estimate_monthly_cost(VolumeType::Gp3, 100, Some(iops), Some(throughput), "us-east-1").unwrap();
+        assert!(after.total_usd <= before.total_usd);
+    }
+
+    #[test]
+    fn test_gp3_rebalance_already_optimal() {
+        assert_eq!(gp3_rebalance(100, 3000, 125, "us-east-1").unwrap(), None);
+        assert_eq!(gp3_rebalance(100, 4000, 1000, "us-east-1").unwrap(), None);
+        assert_eq!(gp3_rebalance(100, 6000, 1500, "us-east-1").unwrap_err(), VolumeLimitError::ThroughputOutOfRange { min: 125, max: 1000, got: 1500 });
+    }
+
+    #[test]
+    fn test_efficiency_gp2_vs_gp3() {
+        let gp2 = calculate(VolumeType::Gp2, 1000, None, None).unwrap();
+        let gp2_cost = estimate_monthly_cost(VolumeType::Gp2, 1000, None, None, "us-east-1").unwrap().total_usd;
+        let gp3 = calculate(VolumeType::Gp3, 1000, None, Some(250)).unwrap();
+        let gp3_cost = estimate_monthly_cost(VolumeType::Gp3, 1000, None, Some(250), "us-east-1").unwrap().total_usd;
+        assert_usd(throughput_efficiency(&gp2, gp2_cost), 2.5);
+        assert_usd(iops_efficiency(&gp2, gp2_cost), 30.0);
+        assert_usd(throughput_efficiency(&gp3, gp3_cost), 250.0 / 85.0);
+        assert!(iops_efficiency(&gp3, gp3_cost) > iops_efficiency(&gp2, gp2_cost));
+    }
+
+    #[test]
+    fn test_efficiency_zero_cost() {
+        let limit = calculate(VolumeType::Gp2, 1000, None, None).unwrap();
+        assert_eq!(throughput_efficiency(&limit, 0.0), 0.0);
+        assert_eq!(iops_efficiency(&limit, -1.0), 0.0);
+    }
+
+    #[test]
+    fn test_gp3_provisioning_surcharge() {
+        assert_usd(gp3_provisioning_surcharge(6000, 250, "us-east-1").unwrap(), 20.0);
+        assert_usd(gp3_provisioning_surcharge(3000, 125, "us-east-1").unwrap(), 0.0);
+        assert_eq!(gp3_provisioning_surcharge(3000, 1000, "us-east-1").unwrap_err(), VolumeLimitError::ThroughputToIopsRatioExceeded { throughput: 1000, iops: 3000, min_iops: 4000 });
+        assert_eq!(gp3_provisioning_surcharge(6000, 250, "mars-north-1").unwrap_err(), VolumeLimitError::UnknownRegion);
+    }
+
+    #[test]
+    fn test_cost_sums_over_10000_volumes() {
+        // gp2 from 1GiB to 10000GiB costs 0.10 USD per GiB, 10000 * 10001 / 2 GiB in total.
+        let gp2_total: f64 = (1..=10000).map(|size| estimate_monthly_cost(VolumeType::Gp2, size, None, None, "us-east-1").unwrap().total_usd).sum();
+        assert_usd_relative(gp2_total, 50_005_000.0 * 0.10);
+
+        // Without targets sc1 is the cheapest type from 125GiB on, at 0.015 USD per GiB.
+        let cheapest_total: f64 = (125..10125).map(|size| compare_costs(size, 0, 0, "us-east-1").unwrap()[0].1).sum();
+        let sizes = (125.0 + 10124.0) * 10000.0 / 2.0;
+        assert_usd_relative(cheapest_total, sizes * 0.015);
+
+        // The largest gp3 volume, one at a time and as a fleet.
+        let largest: f64 = (0..10000).map(|_| estimate_monthly_cost(VolumeType::Gp3, 16384, Some(16000), Some(1000), "us-east-1").unwrap().total_usd).sum();
+        let fleet = estimate_fleet_cost(VolumeType::Gp3, 16384, Some(16000), Some(1000), 10000, "us-east-1").unwrap();
+        let expected = 10000.0 * (16384.0 * 0.08 + 13000.0 * 0.005 + 875.0 * 0.04);
+        assert_usd_relative(largest, expected);
+        assert_usd_relative(fleet.total_usd, expected);
+    }
+
+    #[test]
+    fn test_estimate_fleet_cost_beyond_u32() {
+        let volume_count = u64::from(u32::MAX) + 1;
+        let cost = estimate_fleet_cost(VolumeType::Gp2, 100, None, None, volume_count, "us-east-1").unwrap();
+        assert_usd(cost.total_usd, 10.0 * 4_294_967_296.0);
+        assert_eq!(estimate_fleet_cost(VolumeType::Gp2, 0, None, None, 1, "us-east-1").unwrap_err(), VolumeLimitError::SizeOutOfRange { min: 1, max: 16384, got: 0 });
+    }
+}
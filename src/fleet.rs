@@ -0,0 +1,228 @@
+use alloc::vec::Vec;
+
+use crate::{calculate, estimate_monthly_cost, Limit, VolumeLimitError, VolumeType};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VolumeRequest {
+    pub volume_type: VolumeType,
+    pub size_gb: u32,
+    pub provisioned_iops: Option<u32>,
+    pub provisioned_throughput: Option<u32>,
+}
+
+impl VolumeRequest {
+    pub fn calculate(&self) -> Result<Limit, VolumeLimitError> {
+        calculate(self.volume_type, self.size_gb, self.provisioned_iops, self.provisioned_throughput)
+    }
+}
+
+/// Volumes accumulated one at a time, each is validated when it's added.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Fleet {
+    requests: Vec<VolumeRequest>,
+    limits: Vec<Limit>,
+}
+
+impl Fleet {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a volume, an invalid configuration is rejected and leaves the fleet unchanged.
+    pub fn add(&mut self, volume_type: VolumeType, size_gb: u32, provisioned_iops: Option<u32>, provisioned_throughput: Option<u32>) -> Result<&mut Self, VolumeLimitError> {
+        let request = VolumeRequest { volume_type, size_gb, provisioned_iops, provisioned_throughput };
+        let limit = request.calculate()?;
+        self.requests.push(request);
+        self.limits.push(limit);
+        Ok(self)
+    }
+
+    /// Sum of the limits of every volume, see `aggregate_limits`.
+    pub fn total(&self) -> Limit {
+        aggregate_limits(&self.limits)
+    }
+
+    /// Monthly cost of every volume in `region`.
+    pub fn cost(&self, region: &str) -> Result<f64, VolumeLimitError> {
+        self.requests.iter().try_fold(0.0, |total, request| {
+            let cost = estimate_monthly_cost(request.volume_type, request.size_gb, request.provisioned_iops, request.provisioned_throughput, region)?;
+            Ok(total + cost.total_usd)
+        })
+    }
+
+    pub fn count(&self) -> usize {
+        self.requests.len()
+    }
+}
+
+/// Calculates every request independently, results are aligned by index with `requests`.
+pub fn calculate_batch(requests: &[VolumeRequest]) -> Vec<Result<Limit, VolumeLimitError>> {
+    requests.iter().map(VolumeRequest::calculate).collect()
+}
+
+/// Lazy version of `calculate_batch`, each request is calculated as the result is consumed.
+pub fn calculate_stream<I: Iterator<Item = VolumeRequest>>(requests: I) -> impl Iterator<Item = Result<Limit, VolumeLimitError>> {
+    requests.map(|request| request.calculate())
+}
+
+/// Sums baseline and burst values of all `limits`, saturating at `u32::MAX`.
+pub fn aggregate_limits(limits: &[Limit]) -> Limit {
+    limits.iter().fold(Limit::default(), |total, limit| Limit {
+        iops: total.iops.saturating_add(limit.iops),
+        speed: total.speed.saturating_add(limit.speed),
+        burst_iops: total.burst_iops.saturating_add(limit.burst_iops),
+        burst_speed: total.burst_speed.saturating_add(limit.burst_speed),
+    })
+}
+
+/// Same as `aggregate_limits`, but fails with `VolumeLimitError::Overflow` instead of saturating.
+pub fn try_aggregate_limits(limits: &[Limit]) -> Result<Limit, VolumeLimitError> {
+    limits.iter().try_fold(Limit::default(), |total, limit| {
+        Ok(Limit {
+            iops: total.iops.checked_add(limit.iops).ok_or(VolumeLimitError::Overflow)?,
+            speed: total.speed.checked_add(limit.speed).ok_or(VolumeLimitError::Overflow)?,
+            burst_iops: total.burst_iops.checked_add(limit.burst_iops).ok_or(VolumeLimitError::Overflow)?,
+            burst_speed: total.burst_speed.checked_add(limit.burst_speed).ok_or(VolumeLimitError::Overflow)?,
+        })
+    })
+}
+
+/// Baseline throughput in MiB/s left unused on an instance, negative when the volumes oversubscribe it.
+pub fn bandwidth_headroom(instance_max_mibps: u32, volumes: &[Limit]) -> i64 {
+    i64::from(instance_max_mibps) - i64::from(aggregate_limits(volumes).speed)
+}
+
+/// How many volumes like `per_volume` fit before their combined baseline throughput exceeds `instance_max_mibps`.
+/// Volumes without baseline throughput never exceed it, so the count is `u32::MAX`.
+pub fn max_volumes_under_cap(per_volume: &Limit, instance_max_mibps: u32) -> u32 {
+    instance_max_mibps.checked_div(per_volume.speed).unwrap_or(u32::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{calculate_gp2_limits, calculate_gp3_limits};
+
+    #[test]
+    fn test_batch_mixed() {
+        let requests = [
+            VolumeRequest { volume_type: VolumeType::Gp3, size_gb: 500, provisioned_iops: Some(6000), provisioned_throughput: None },
+            VolumeRequest { volume_type: VolumeType::Io1, size_gb: 500, provisioned_iops: Some(70000), provisioned_throughput: None },
+            VolumeRequest { volume_type: VolumeType::Gp3, size_gb: 20, provisioned_iops: None, provisioned_throughput: None },
+        ];
+        let results = calculate_batch(&requests);
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0], Ok(Limit { iops: 6000, speed: 125, burst_iops: 0, burst_speed: 0 }));
+        assert_eq!(results[1], Err(VolumeLimitError::IopsOutOfRange { min: 100, max: 64000, got: 70000 }));
+        assert_eq!(results[2], Ok(Limit { iops: 3000, speed: 125, burst_iops: 0, burst_speed: 0 }));
+    }
+
+    #[test]
+    fn test_batch_empty() {
+        assert!(calculate_batch(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_aggregate_gp3_and_two_gp2() {
+        let limits = [
+            calculate_gp3_limits(500, Some(6000), Some(250)).unwrap(),
+            calculate_gp2_limits(20).unwrap(),
+            calculate_gp2_limits(1500).unwrap(),
+        ];
+        assert_eq!(aggregate_limits(&limits), Limit { iops: 10600, speed: 525, burst_iops: 3000, burst_speed: 128 });
+    }
+
+    #[test]
+    fn test_aggregate_saturates() {
+        let limits = [Limit { iops: u32::MAX, speed: 1, burst_iops: 0, burst_speed: 0 }, Limit { iops: 1, speed: 1, burst_iops: 0, burst_speed: 0 }];
+        assert_eq!(aggregate_limits(&limits), Limit { iops: u32::MAX, speed: 2, burst_iops: 0, burst_speed: 0 });
+        assert_eq!(aggregate_limits(&[]), Limit::default());
+    }
+
+    #[test]
+    fn test_bandwidth_headroom_oversubscribed() {
+        let volume = calculate_gp3_limits(500, None, Some(250)).unwrap();
+        let volumes = [volume.clone(), volume.clone(), volume];
+        assert_eq!(bandwidth_headroom(566, &volumes), -184);
+        assert_eq!(bandwidth_headroom(1000, &volumes), 250);
+        assert_eq!(bandwidth_headroom(566, &[]), 566);
+    }
+
+    #[test]
+    fn test_try_aggregate_near_max() {
+        let near_max = Limit { iops: u32::MAX - 1, speed: 1, burst_iops: 0, burst_speed: 0 };
+        let one = Limit { iops: 1, speed: 1, burst_iops: 0, burst_speed: 0 };
+        assert_eq!(try_aggregate_limits(&[near_max.clone(), one.clone()]), Ok(Limit { iops: u32::MAX, speed: 2, burst_iops: 0, burst_speed: 0 }));
+        assert_eq!(try_aggregate_limits(&[near_max, one.clone(), one]), Err(VolumeLimitError::Overflow));
+        assert_eq!(try_aggregate_limits(&[]), Ok(Limit::default()));
+    }
+
+    #[test]
+    fn test_max_volumes_under_cap() {
+        let volume = calculate_gp2_limits(2000).unwrap();
+        assert_eq!(volume.speed, 250);
+        assert_eq!(max_volumes_under_cap(&volume, 1187), 4);
+        assert_eq!(max_volumes_under_cap(&volume, 1250), 5);
+        assert_eq!(max_volumes_under_cap(&volume, 249), 0);
+        assert_eq!(max_volumes_under_cap(&Limit::default(), 1187), u32::MAX);
+    }
+
+    #[test]
+    fn test_stream_is_lazy() {
+        let pulled = core::cell::Cell::new(0);
+        let requests = [
+            VolumeRequest { volume_type: VolumeType::Gp2, size_gb: 100, provisioned_iops: None, provisioned_throughput: None },
+            VolumeRequest { volume_type: VolumeType::Io1, size_gb: 100, provisioned_iops: None, provisioned_throughput: None },
+            VolumeRequest { volume_type: VolumeType::Gp3, size_gb: 100, provisioned_iops: None, provisioned_throughput: None },
+        ];
+        let mut results = calculate_stream(requests.into_iter().inspect(|_| pulled.set(pulled.get() + 1)));
+        assert_eq!(pulled.get(), 0);
+        assert_eq!(results.next(), Some(Ok(calculate_gp2_limits(100).unwrap())));
+        assert_eq!(results.next(), Some(Err(VolumeLimitError::MissingProvisionedIops)));
+        assert_eq!(pulled.get(), 2);
+        assert_eq!(results.next(), Some(Ok(calculate_gp3_limits(100, None, None).unwrap())));
+        assert_eq!(results.next(), None);
+        assert_eq!(pulled.get(), 3);
+    }
+
+    #[test]
+    fn test_fleet() {
+        let mut fleet = Fleet::new();
+        fleet.add(VolumeType::Gp3, 500, Some(6000), Some(250)).unwrap()
+            .add(VolumeType::Gp2, 20, None, None).unwrap()
+            .add(VolumeType::Gp2, 1500, None, None).unwrap();
+        assert_eq!(fleet.count(), 3);
+        assert_eq!(fleet.total(), Limit { iops: 10600, speed: 525, burst_iops: 3000, burst_speed: 128 });
+        let cost = fleet.cost("us-east-1").unwrap();
+        assert!((cost - (40.0 + 15.0 + 5.0 + 2.0 + 150.0)).abs() < 1e-9);
+        assert_eq!(fleet.cost("mars-north-1").unwrap_err(), VolumeLimitError::UnknownRegion);
+    }
+
+    #[test]
+    fn test_fleet_rejects_invalid_volume() {
+        let mut fleet = Fleet::new();
+        assert_eq!(fleet.add(VolumeType::Io1, 500, None, None).unwrap_err(), VolumeLimitError::MissingProvisionedIops);
+        assert_eq!(fleet.count(), 0);
+        assert_eq!(fleet.total(), Limit::default());
+    }
+
+    #[test]
+    fn test_fleet_cost_10000_volumes() {
+        // Largest gp3 and io2 volumes alternate, so every product and the running sum are as large as they get.
+        let mut fleet = Fleet::new();
+        for i in 0..10000 {
+            if i % 2 == 0 {
+                fleet.add(VolumeType::Gp3, 16384, Some(16000), Some(1000)).unwrap();
+            } else {
+                fleet.add(VolumeType::Io2, 16384, Some(64000), None).unwrap();
+            }
+        }
+        assert_eq!(fleet.count(), 10000);
+
+        let gp3_usd = 16384.0 * 0.08 + 13000.0 * 0.005 + 875.0 * 0.04;
+        let io2_usd = 16384.0 * 0.125 + 32000.0 * 0.065 + 32000.0 * 0.0455;
+        let expected = 5000.0 * (gp3_usd + io2_usd);
+        let cost = fleet.cost("us-east-1").unwrap();
+        assert!((cost - expected).abs() < expected * 1e-12, "expected {} USD, got {} USD", expected, cost);
+    }
+}
@@ -0,0 +1,76 @@
+use crate::{Limit, VolumeLimitError};
+
+// Maximum dedicated EBS throughput (MiB/s, converted from the documented MB/s) and IOPS per instance type.
+const INSTANCE_EBS_LIMITS: &[(&str, u32, u32)] = &[
+    ("m5.large", 566, 18750),
+    ("m5.xlarge", 566, 18750),
+    ("m5.2xlarge", 566, 18750),
+    ("m5.4xlarge", 566, 18750),
+    ("m5.8xlarge", 810, 30000),
+    ("m5.12xlarge", 1132, 40000),
+    ("m5.16xlarge", 1621, 60000),
+    ("m5.24xlarge", 2264, 80000),
+    ("c5.large", 566, 20000),
+    ("c5.xlarge", 566, 20000),
+    ("c5.2xlarge", 566, 20000),
+    ("c5.4xlarge", 566, 20000),
+    ("c5.9xlarge", 1132, 40000),
+    ("c5.12xlarge", 1132, 40000),
+    ("c5.18xlarge", 2264, 80000),
+    ("c5.24xlarge", 2264, 80000),
+    ("r5.large", 566, 18750),
+    ("r5.xlarge", 566, 18750),
+    ("r5.2xlarge", 566, 18750),
+    ("r5.4xlarge", 566, 18750),
+    ("r5.8xlarge", 810, 30000),
+    ("r5.12xlarge", 1132, 40000),
+    ("r5.16xlarge", 1621, 60000),
+    ("r5.24xlarge", 2264, 80000),
+];
+
+/// Whether the aggregated baseline throughput or IOPS of the attached volumes exceeds
+/// the maximum EBS bandwidth of `instance_type`.
+pub fn exceeds_instance_bandwidth(total: &Limit, instance_type: &str) -> Result<bool, VolumeLimitError> {
+    let (_, max_speed, max_iops) = INSTANCE_EBS_LIMITS.iter()
+        .find(|(name, _, _)| *name == instance_type)
+        .ok_or(VolumeLimitError::UnknownInstanceType)?;
+    Ok(total.speed > *max_speed || total.iops > *max_iops)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{aggregate_limits, calculate_gp2_limits, calculate_gp3_limits};
+
+    #[test]
+    fn test_m5_large_throughput_exceeded() {
+        let total = aggregate_limits(&[
+            calculate_gp2_limits(1500).unwrap(),
+            calculate_gp2_limits(1500).unwrap(),
+            calculate_gp2_limits(1500).unwrap(),
+        ]);
+        assert!(exceeds_instance_bandwidth(&total, "m5.large").unwrap());
+        assert!(!exceeds_instance_bandwidth(&total, "m5.24xlarge").unwrap());
+    }
+
+    #[test]
+    fn test_m5_large_iops_exceeded() {
+        let total = aggregate_limits(&[
+            calculate_gp3_limits(1000, Some(16000), None).unwrap(),
+            calculate_gp3_limits(1000, Some(16000), None).unwrap(),
+        ]);
+        assert!(exceeds_instance_bandwidth(&total, "m5.large").unwrap());
+        assert!(!exceeds_instance_bandwidth(&total, "c5.9xlarge").unwrap());
+    }
+
+    #[test]
+    fn test_within_bandwidth() {
+        let total = calculate_gp2_limits(1500).unwrap();
+        assert!(!exceeds_instance_bandwidth(&total, "c5.xlarge").unwrap());
+    }
+
+    #[test]
+    fn test_unknown_instance_type() {
+        assert_eq!(exceeds_instance_bandwidth(&Limit::default(), "x9.huge").unwrap_err(), VolumeLimitError::UnknownInstanceType);
+    }
+}
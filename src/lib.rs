@@ -1,7 +1,38 @@
-use std::cmp::{max, min};
-use std::error::Error;
+#![cfg_attr(all(feature = "no_std", not(test)), no_std)]
 
-#[derive(Debug)]
+extern crate alloc;
+
+use alloc::format;
+use alloc::string::String;
+use alloc::vec::Vec;
+use core::error::Error;
+use core::fmt;
+use core::str::FromStr;
+
+mod cost;
+mod fleet;
+mod instance;
+mod observer;
+mod region;
+mod report;
+#[cfg(any(feature = "wasm", test))]
+mod wasm;
+
+pub use cost::{cheapest_sizing, compare_costs, estimate_fleet_cost, estimate_monthly_cost, gp3_provisioning_surcharge, gp3_rebalance, iops_efficiency, throughput_efficiency, CostEstimate};
+pub use fleet::{aggregate_limits, bandwidth_headroom, calculate_batch, calculate_stream, max_volumes_under_cap, try_aggregate_limits, Fleet, VolumeRequest};
+pub use instance::exceeds_instance_bandwidth;
+pub use observer::{calculate_observed, LimitObserver, NoopObserver};
+pub use region::max_limits;
+pub use report::{format_table, limits_to_csv};
+#[cfg(feature = "wasm")]
+pub use wasm::calculate_gp3_wasm;
+
+const MB_PER_MIB: f64 = 1.048576;        // 1MiB is 1024 * 1024 bytes, 1MB is 1000 * 1000 bytes
+
+/// Performance limits of a volume. Limits are ordered by baseline IOPS, then baseline throughput, then
+/// burst IOPS and burst throughput, so sorting puts the most capable volume last.
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Limit {
     pub iops: u32,
     pub speed: u32,
@@ -10,224 +41,2484 @@ pub struct Limit {
 }
 
 impl Limit {
-    pub fn default() -> Limit {
-        Limit{
-            iops: 0,
-            speed: 0,
-            burst_iops: 0,
-            burst_speed: 0
+    /// Serializes the limit as a compact JSON object, keys are always in field order.
+    pub fn to_json(&self) -> String {
+        format!(r#"{{"iops":{},"speed":{},"burst_iops":{},"burst_speed":{}}}"#, self.iops, self.speed, self.burst_iops, self.burst_speed)
+    }
+
+    /// Baseline throughput in decimal MB/s.
+    pub fn speed_mb(&self) -> f64 {
+        f64::from(self.speed) * MB_PER_MIB
+    }
+
+    /// Burst throughput in decimal MB/s.
+    pub fn burst_speed_mb(&self) -> f64 {
+        f64::from(self.burst_speed) * MB_PER_MIB
+    }
+
+    /// Whether the volume can burst above its baseline. Burst fields are zero for types without burst,
+    /// which are gp3, io1 and io2, as well as gp2 larger than 1000GiB whose baseline already meets the burst.
+    pub fn has_burst(&self) -> bool {
+        self.burst_iops != 0 || self.burst_speed != 0
+    }
+
+    /// Whether the baseline reaches both `iops` and `speed` in MiB/s.
+    pub fn meets(&self, iops: u32, speed: u32) -> bool {
+        self.iops >= iops && self.speed >= speed
+    }
+
+    /// Same as `meets`, also accepting targets that are only reached while bursting.
+    pub fn meets_with_burst(&self, iops: u32, speed: u32) -> bool {
+        max(self.iops, self.burst_iops) >= iops && max(self.speed, self.burst_speed) >= speed
+    }
+
+    /// Splits the limit into baseline and burst, with no burst when both burst fields are zero.
+    #[allow(clippy::wrong_self_convention)]
+    pub fn into_performance(&self) -> Performance {
+        let burst = if self.has_burst() {
+            Some(IopsThroughput { iops: self.burst_iops, speed: self.burst_speed })
+        } else {
+            None
+        };
+        Performance { baseline: IopsThroughput { iops: self.iops, speed: self.speed }, burst }
+    }
+
+    /// Signed change from this limit to `other`, negative values are a downgrade.
+    pub fn diff(&self, other: &Limit) -> LimitDiff {
+        LimitDiff {
+            iops: i64::from(other.iops) - i64::from(self.iops),
+            speed: i64::from(other.speed) - i64::from(self.speed),
+            burst_iops: i64::from(other.burst_iops) - i64::from(self.burst_iops),
+            burst_speed: i64::from(other.burst_speed) - i64::from(self.burst_speed),
+        }
+    }
+
+    /// Baseline IOPS and throughput of this limit as multiples of `reference`. A zero in the reference
+    /// gives infinity, or 0.0 when this limit is zero as well.
+    pub fn ratio_to(&self, reference: &Limit) -> (f64, f64) {
+        (fraction(self.iops, reference.iops), fraction(self.speed, reference.speed))
+    }
+}
+
+/// `(iops, speed, burst_iops, burst_speed)`, in field order.
+impl From<Limit> for (u32, u32, u32, u32) {
+    fn from(limit: Limit) -> Self {
+        (limit.iops, limit.speed, limit.burst_iops, limit.burst_speed)
+    }
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} IOPS, {} MiB/s throughput", self.iops, self.speed)?;
+        if self.has_burst() {
+            write!(f, " (burst: {} IOPS, {} MiB/s)", self.burst_iops, self.burst_speed)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct IopsThroughput {
+    pub iops: u32,
+    pub speed: u32,
+}
+
+/// Baseline and optional burst performance, as returned by `Limit::into_performance`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Performance {
+    pub baseline: IopsThroughput,
+    pub burst: Option<IopsThroughput>,
+}
+
+/// Difference between two limits, as returned by `Limit::diff`.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct LimitDiff {
+    pub iops: i64,
+    pub speed: i64,
+    pub burst_iops: i64,
+    pub burst_speed: i64,
+}
+
+impl fmt::Display for LimitDiff {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{:+} IOPS, {:+} MiB/s", self.iops, self.speed)?;
+        if self.burst_iops != 0 || self.burst_speed != 0 {
+            write!(f, " (burst: {:+} IOPS, {:+} MiB/s)", self.burst_iops, self.burst_speed)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VolumeLimitError {
+    SizeOutOfRange { min: u32, max: u32, got: u32 },
+    IopsOutOfRange { min: u32, max: u32, got: u32 },
+    IopsBelowBaseline { baseline: u32, got: u32 },
+    ThroughputOutOfRange { min: u32, max: u32, got: u32 },
+    IopsToSizeRatioExceeded { max_ratio: u32, iops: u32, size: u32 },
+    ThroughputToIopsRatioExceeded { throughput: u32, iops: u32, min_iops: u32 },
+    MissingProvisionedIops,
+    IopsTargetUnreachable { max: u32, target: u32 },
+    ThroughputTargetUnreachable { max: u32, target: u32 },
+    NoEligibleVolumeType,
+    UnknownRegion,
+    UnknownInstanceType,
+    UnknownVolumeType,
+    Overflow,
+    NegativeParameter { name: &'static str, got: i32 },
+    UnparseableSize,
+}
+
+impl fmt::Display for VolumeLimitError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            VolumeLimitError::SizeOutOfRange { min, max, got } =>
+                write!(f, "Volume size can not be less than {}GiB or greater than {}GiB, got {}GiB.", min, max, got),
+            VolumeLimitError::IopsOutOfRange { min, max, got } =>
+                write!(f, "Provisioned IOPS can not be less than {} or greater than {}, got {}.", min, max, got),
+            VolumeLimitError::IopsBelowBaseline { baseline, got } =>
+                write!(f, "Provisioned IOPS can not be less than the {} IOPS baseline, got {}. Leave IOPS unset to get the baseline at no extra cost.", baseline, got),
+            VolumeLimitError::ThroughputOutOfRange { min, max, got } =>
+                write!(f, "Provisioned throughput can not be less than {}MiB/s or greater than {}MiB/s, got {}MiB/s.", min, max, got),
+            VolumeLimitError::IopsToSizeRatioExceeded { max_ratio, iops, size } =>
+                write!(f, "Maximum ratio of {}:1 is permitted between IOPS and volume size, got {} IOPS for {}GiB.", max_ratio, iops, size),
+            VolumeLimitError::ThroughputToIopsRatioExceeded { throughput, iops, min_iops } =>
+                write!(f, "Maximum ratio of 0.25:1 is permitted between throughput (MiBps) and IOPS, got {}MiB/s for {} IOPS. Provision at least {} IOPS for this throughput.", throughput, iops, min_iops),
+            VolumeLimitError::MissingProvisionedIops =>
+                write!(f, "Provisioned IOPS are required for io1 and io2 volume types."),
+            VolumeLimitError::IopsTargetUnreachable { max, target } =>
+                write!(f, "Target of {} IOPS exceeds the maximum of {} IOPS for this volume type, consider io1 or io2.", target, max),
+            VolumeLimitError::ThroughputTargetUnreachable { max, target } =>
+                write!(f, "Target of {}MiB/s exceeds the maximum of {}MiB/s for this volume type.", target, max),
+            VolumeLimitError::NoEligibleVolumeType =>
+                write!(f, "No volume type can satisfy the requested IOPS and throughput at this volume size."),
+            VolumeLimitError::UnknownRegion =>
+                write!(f, "Pricing is not available for this region."),
+            VolumeLimitError::UnknownInstanceType =>
+                write!(f, "EBS bandwidth is not known for this instance type."),
+            VolumeLimitError::UnknownVolumeType =>
+                write!(f, "Unknown volume type, expected one of gp2, gp3, io1, io2, st1, sc1 or standard."),
+            VolumeLimitError::Overflow =>
+                write!(f, "Result does not fit in 32 bits."),
+            VolumeLimitError::NegativeParameter { name, got } =>
+                write!(f, "{} can not be negative, got {}.", name, got),
+            VolumeLimitError::UnparseableSize =>
+                write!(f, "Volume size must be a non-negative number with an optional GiB, TiB, GB or TB unit."),
+        }
+    }
+}
+
+impl Error for VolumeLimitError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "lowercase"))]          // Same strings as the AWS API and `Display`
+pub enum VolumeType {
+    Gp2,
+    Gp3,
+    Io1,
+    Io2,
+    St1,
+    Sc1,
+    Standard,
+}
+
+impl VolumeType {
+    pub const ALL: [VolumeType; 7] = [
+        VolumeType::Gp2,
+        VolumeType::Gp3,
+        VolumeType::Io1,
+        VolumeType::Io2,
+        VolumeType::St1,
+        VolumeType::Sc1,
+        VolumeType::Standard,
+    ];
+
+    fn as_str(self) -> &'static str {
+        match self {
+            VolumeType::Gp2 => "gp2",
+            VolumeType::Gp3 => "gp3",
+            VolumeType::Io1 => "io1",
+            VolumeType::Io2 => "io2",
+            VolumeType::St1 => "st1",
+            VolumeType::Sc1 => "sc1",
+            VolumeType::Standard => "standard",
         }
     }
 }
 
-pub fn calculate_gp2_limits(volume_size_gb: u32) -> Result<Limit, Box<dyn Error>> {
-    if (volume_size_gb < 1) || (volume_size_gb > 16384) {
-        return Err("Volume size for gp2 can not be less than 1GiB or greater than 16384GiB")?;
+impl fmt::Display for VolumeType {
+    /// Formats the AWS volume type identifier, e.g. `gp3`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for VolumeType {
+    type Err = VolumeLimitError;
+
+    /// Parses the AWS volume type identifier, ignoring case.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        VolumeType::ALL.into_iter()
+            .find(|volume_type| volume_type.as_str().eq_ignore_ascii_case(s))
+            .ok_or(VolumeLimitError::UnknownVolumeType)
+    }
+}
+
+// core::cmp::{max, min} can't be called from const fn.
+const fn min(a: u32, b: u32) -> u32 {
+    if a < b { a } else { b }
+}
+
+const fn max(a: u32, b: u32) -> u32 {
+    if a > b { a } else { b }
+}
+
+/// Largest I/O size in KiB gp2 baseline throughput is derived with.
+pub const GP2_MAX_BLOCK_KIB: u32 = 256;
+/// Largest I/O size in KiB io1/io2 throughput is derived with below 32000 IOPS.
+pub const IO_MAX_BLOCK_KIB: u32 = 256;
+/// Largest I/O size in KiB io1/io2 throughput is derived with at 32000 IOPS or more.
+pub const IO_HIGH_IOPS_MAX_BLOCK_KIB: u32 = 16;
+
+// IOPS per MiB/s of throughput at the largest I/O size of each model.
+const GP2_IOPS_PER_MIBPS: u32 = 1024 / GP2_MAX_BLOCK_KIB;
+const IO_IOPS_PER_MIBPS: u32 = 1024 / IO_MAX_BLOCK_KIB;
+const IO_HIGH_IOPS_PER_MIBPS: u32 = 1024 / IO_HIGH_IOPS_MAX_BLOCK_KIB;
+
+// Throughput in MiB/s of `iops` I/Os of `block_size_kib` each, rounded down.
+const fn throughput_for_block_size(iops: u32, block_size_kib: u32) -> u32 {
+    let mibps = iops as u64 * block_size_kib as u64 / 1024;
+    if mibps > u32::MAX as u64 { u32::MAX } else { mibps as u32 }
+}
+
+pub const GP2_MAX_IOPS: u32 = 16000;
+pub const GP2_MAX_THROUGHPUT: u32 = 250;
+pub const GP3_MAX_IOPS: u32 = 16000;
+pub const GP3_MAX_THROUGHPUT: u32 = 1000;
+pub const IO1_MAX_IOPS: u32 = 64000;
+pub const IO1_MAX_THROUGHPUT: u32 = 1000;
+pub const IO2_MAX_IOPS: u32 = 64000;
+pub const IO2_MAX_THROUGHPUT: u32 = 1000;
+pub const IO2_BLOCK_EXPRESS_MAX_IOPS: u32 = 256000;
+pub const IO2_BLOCK_EXPRESS_MAX_THROUGHPUT: u32 = 4000;
+/// Maximum ratio between provisioned io2 Block Express IOPS and the size in GiB.
+pub const IO2_BLOCK_EXPRESS_MAX_IOPS_PER_GIB: u32 = 1000;
+/// io2 Block Express throughput in MiB/s per 1000 provisioned IOPS.
+pub const IO2_BLOCK_EXPRESS_MIBPS_PER_1000_IOPS: u32 = 256;
+/// Provisioned IOPS from which io1/io2 throughput is derived with `IO_HIGH_IOPS_MAX_BLOCK_KIB` I/Os,
+/// below it throughput is capped at `IO_LOW_IOPS_MAX_THROUGHPUT`.
+pub const IO_HIGH_IOPS_THRESHOLD: u32 = 32000;
+pub const IO_LOW_IOPS_MAX_THROUGHPUT: u32 = 500;
+pub const ST1_MAX_THROUGHPUT: u32 = 500;
+/// Baseline throughput ceiling of sc1, bursts can go up to `SC1_MAX_BURST_THROUGHPUT`.
+pub const SC1_MAX_THROUGHPUT: u32 = 192;
+pub const SC1_MAX_BURST_THROUGHPUT: u32 = 250;
+
+pub const GP2_MIN_SIZE_GB: u32 = 1;
+pub const GP2_MAX_SIZE_GB: u32 = 16384;
+/// Baseline IOPS a gp2 volume gets per GiB, never below `GP2_MIN_IOPS`.
+pub const GP2_IOPS_PER_GIB: u32 = 3;
+pub const GP2_MIN_IOPS: u32 = 100;
+pub const GP3_MIN_SIZE_GB: u32 = 1;
+pub const GP3_MAX_SIZE_GB: u32 = 16384;
+/// Maximum ratio between provisioned gp3 IOPS and the size in GiB.
+pub const GP3_MAX_IOPS_PER_GIB: u32 = 500;
+/// Provisioned gp3 IOPS each MiB/s of provisioned throughput requires, from the 0.25:1 throughput to IOPS ratio.
+pub const GP3_IOPS_PER_MIBPS: u32 = 4;
+/// Size range of io1 and io2 volumes.
+pub const IO_MIN_SIZE_GB: u32 = 4;
+pub const IO_MAX_SIZE_GB: u32 = 16384;
+/// Fewest IOPS an io1 or io2 volume can be provisioned with.
+pub const IO_MIN_IOPS: u32 = 100;
+/// Maximum ratio between provisioned io1 IOPS and the size in GiB.
+pub const IO1_MAX_IOPS_PER_GIB: u32 = 50;
+/// Maximum ratio between provisioned io2 IOPS and the size in GiB.
+pub const IO2_MAX_IOPS_PER_GIB: u32 = 500;
+/// Baseline and burst throughput in MiB/s per TiB of st1 and sc1, up to their ceilings.
+pub const ST1_THROUGHPUT_PER_TIB: u32 = 40;
+pub const ST1_BURST_THROUGHPUT_PER_TIB: u32 = 250;
+pub const SC1_THROUGHPUT_PER_TIB: u32 = 12;
+pub const SC1_BURST_THROUGHPUT_PER_TIB: u32 = 80;
+/// Size range of st1 and sc1 volumes.
+pub const HDD_MIN_SIZE_GB: u32 = 125;
+pub const HDD_MAX_SIZE_GB: u32 = 16384;
+pub const STANDARD_MIN_SIZE_GB: u32 = 1;
+pub const STANDARD_MAX_SIZE_GB: u32 = 1024;
+/// Baseline and burst limits of magnetic volumes, which don't depend on the size.
+pub const STANDARD_IOPS: u32 = 100;
+pub const STANDARD_THROUGHPUT: u32 = 40;
+pub const STANDARD_BURST_IOPS: u32 = 200;
+pub const STANDARD_BURST_THROUGHPUT: u32 = 90;
+
+/// Largest gp2 size that earns burst credits, larger volumes have a baseline of at least the burst IOPS.
+pub const GP2_BURST_MAX_SIZE_GB: u32 = 1000;
+/// Largest gp2 size in the lower throughput tier, capped at `GP2_SMALL_TIER_MAX_THROUGHPUT`.
+///
+/// AWS caps volumes of 170GiB or smaller at 128MiB/s, so 170GiB itself is in the lower tier.
+/// The IOPS derived throughput only reaches 128MiB/s at 171GiB (513 / 4), the first size with the 250MiB/s cap,
+/// so the baseline never hits the 128MiB/s cap and only the burst throughput changes across the boundary.
+pub const GP2_SMALL_TIER_MAX_SIZE_GB: u32 = 170;
+pub const GP2_SMALL_TIER_MAX_THROUGHPUT: u32 = 128;
+
+/// IOPS every gp2 volume of 1000GiB or less can burst to.
+pub const GP2_BURST_IOPS: u32 = 3000;
+/// IOPS a gp3 volume gets when none are provisioned.
+pub const GP3_BASELINE_IOPS: u32 = 3000;
+/// Throughput in MiB/s a gp3 volume gets when none is provisioned.
+pub const GP3_BASELINE_THROUGHPUT: u32 = 125;
+
+pub const fn calculate_gp2_limits(volume_size_gb: u32) -> Result<Limit, VolumeLimitError> {
+    if volume_size_gb < GP2_MIN_SIZE_GB || volume_size_gb > GP2_MAX_SIZE_GB {
+        return Err(VolumeLimitError::SizeOutOfRange { min: GP2_MIN_SIZE_GB, max: GP2_MAX_SIZE_GB, got: volume_size_gb });
     }
 
-    if volume_size_gb > 1000 {
-        let max_available_iops = 16000;                // Max IOPS available for this volume type
-        let max_available_throughput = 250;            // Max throughput available for this volume type
-        let calculate_iops = 3 * volume_size_gb;
+    if volume_size_gb > GP2_BURST_MAX_SIZE_GB {
+        let max_available_iops = GP2_MAX_IOPS;         // Max IOPS available for this volume type
+        let max_available_throughput = GP2_MAX_THROUGHPUT;    // Max throughput available for this volume type
+        let calculate_iops = GP2_IOPS_PER_GIB * volume_size_gb;
         let baseline_iops = min(calculate_iops, max_available_iops);             // Baseline for Gp2 can not be more than max_available_iops
         let baseline_throughput = max_available_throughput;   // For volumes greater than 1000GiB, max throughput is always 250MiB/s.
-        return Ok(Limit { iops: baseline_iops, speed: baseline_throughput, burst_iops: 0, burst_speed: 0 });
+        Ok(Limit { iops: baseline_iops, speed: baseline_throughput, burst_iops: 0, burst_speed: 0 })
     } else {
-        let burst = 3000;
-        if volume_size_gb < 170 {
-            let max_available_throughput = 128;                            // Gp2 volumes of size less than 170GiB have a throughput cap at 128MiB/s
-            let calculate_iops = 3 * volume_size_gb;
-            let baseline_iops = max(calculate_iops, 100);                // Baseline for Gp2 can not be less than 100.
-            let calculate_tp = baseline_iops / 4;  // Calculating throughput from IOPS with max block size as 256KiB
+        let burst = GP2_BURST_IOPS;
+        // See `GP2_SMALL_TIER_MAX_SIZE_GB` for why the boundary is inclusive
+        if volume_size_gb <= GP2_SMALL_TIER_MAX_SIZE_GB {
+            let max_available_throughput = GP2_SMALL_TIER_MAX_THROUGHPUT;  // Gp2 volumes of 170GiB or smaller have a throughput cap at 128MiB/s
+            let calculate_iops = GP2_IOPS_PER_GIB * volume_size_gb;
+            let baseline_iops = max(calculate_iops, GP2_MIN_IOPS);       // Baseline for Gp2 can not be less than 100.
+            let calculate_tp = throughput_for_block_size(baseline_iops, GP2_MAX_BLOCK_KIB);  // Calculating throughput from IOPS with max block size as 256KiB
             let baseline_throughput = min(max_available_throughput, calculate_tp);      // Throughput can not exceed max_available_throughput
-            return Ok(Limit { iops: baseline_iops, speed: baseline_throughput, burst_iops: burst, burst_speed: max_available_throughput });
+            Ok(Limit { iops: baseline_iops, speed: baseline_throughput, burst_iops: burst, burst_speed: max_available_throughput })
         } else {
-            let max_available_throughput = 250;
-            let calculate_iops = 3 * volume_size_gb;
+            let max_available_throughput = GP2_MAX_THROUGHPUT;
+            let calculate_iops = GP2_IOPS_PER_GIB * volume_size_gb;
             let baseline_iops = calculate_iops;
-            let calculate_tp = baseline_iops / 4;
+            let calculate_tp = throughput_for_block_size(baseline_iops, GP2_MAX_BLOCK_KIB);
             let baseline_throughput = min(max_available_throughput, calculate_tp);      // Throughput can not exceed max_available_throughput
-            return Ok(Limit { iops: baseline_iops, speed: baseline_throughput, burst_iops: burst, burst_speed: max_available_throughput });
+            Ok(Limit { iops: baseline_iops, speed: baseline_throughput, burst_iops: burst, burst_speed: max_available_throughput })
         }
     }
 }
 
 
-pub fn calculate_gp3_limits(volume_size_gb: u32, volume_provisioned_iops: Option<u32>, volume_provisioned_throughput: Option<u32>) -> Result<Limit, Box<dyn Error>> {
-    if (volume_size_gb < 1) || (volume_size_gb > 16384) {
-        return Err("Volume size for gp3 can not be less than 1GiB or greater than 16384GiB")?;
+/// Same as `calculate_gp2_limits`, but sizes outside 1..=16384GiB are clamped to the nearest valid size.
+pub const fn calculate_gp2_limits_clamped(volume_size_gb: u32) -> Limit {
+    let volume_size_gb = if volume_size_gb < GP2_MIN_SIZE_GB { GP2_MIN_SIZE_GB } else { min(volume_size_gb, GP2_MAX_SIZE_GB) };
+    match calculate_gp2_limits(volume_size_gb) {
+        Ok(limit) => limit,
+        Err(_) => unreachable!(),
     }
+}
 
-    let volume_iops = if volume_provisioned_iops.is_none() {
-        // Set IOPS as Baseline(3000) for gp3 volume if it was created using CLI without provisioned IOPS .
-        3000
+/// `(size_gb, baseline_iops)` pairs of gp2 from 1GiB upwards in steps of `step_gb`, empty for a zero step.
+pub fn gp2_iops_curve(step_gb: u32) -> impl Iterator<Item = (u32, u32)> {
+    let sizes = if step_gb == 0 {
+        None                                // step_by panics on a zero step, so there are no sizes at all
     } else {
-        let iops = volume_provisioned_iops.unwrap();
-        if (iops < 3000) || (iops > 64000) {
-            return Err("Provisioned IOPS can not be less than 3000 or greater than 16000 for Gp3 volume type..")?;
-        }
+        Some((GP2_MIN_SIZE_GB..=GP2_MAX_SIZE_GB).step_by(step_gb as usize))
+    };
+    sizes.into_iter().flatten().map(|size| (size, calculate_gp2_limits_clamped(size).iops))
+}
 
-        if iops / volume_size_gb > 500 {
-            return Err("Maximum ratio of 500:1 is permitted between IOPS and volume size for Gp3 volume type.")?;
-        }
+/// The rule that determined the result of a calculation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BindingConstraint {
+    /// IOPS are capped at the maximum of the volume type.
+    IopsCeiling,
+    /// Throughput is capped at the maximum of the volume type.
+    ThroughputCeiling,
+    /// Throughput is derived from IOPS and stays below the ceiling.
+    IopsToThroughputConversion,
+    /// Nothing capped the result.
+    None,
+}
 
-        iops
-    };
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DetailedLimit {
+    pub limit: Limit,
+    pub binding_constraint: BindingConstraint,
+    /// Highest IOPS the volume can reach, including burst.
+    pub max_iops: u32,
+    /// Highest throughput in MiB/s the volume can reach, including burst.
+    pub max_speed: u32,
+}
 
-    let volume_throughput = if volume_provisioned_throughput.is_none() {
-        // Set Throughput as Baseline(125MiB/s) for gp3 volume if it was created using CLI without provisioned Throughput .
-        125
+/// Same as `calculate_gp2_limits`, also reporting what capped the baseline.
+pub fn calculate_gp2_limits_detailed(volume_size_gb: u32) -> Result<DetailedLimit, VolumeLimitError> {
+    let limit = calculate_gp2_limits(volume_size_gb)?;
+    let binding_constraint = if gp2_reaches_iops_cap(volume_size_gb) {
+        BindingConstraint::IopsCeiling
+    } else if limit.speed == GP2_MAX_THROUGHPUT {
+        BindingConstraint::ThroughputCeiling
     } else {
-        let throughput = volume_provisioned_throughput.unwrap();
-        if (throughput < 125) || (throughput > 1000) {
-            return Err("Provisioned throughput can not be less than 125MiB/s or greater than 1000MiB/s for Gp3 volume type..")?;
+        BindingConstraint::IopsToThroughputConversion
+    };
+    let max_iops = max(limit.iops, limit.burst_iops);
+    let max_speed = max(limit.speed, limit.burst_speed);
+    Ok(DetailedLimit { limit, binding_constraint, max_iops, max_speed })
+}
+
+/// Smallest gp2 size whose baseline reaches the 250MiB/s throughput cap.
+pub const fn gp2_throughput_cap_size() -> u32 {
+    (GP2_MAX_THROUGHPUT * GP2_IOPS_PER_MIBPS).div_ceil(GP2_IOPS_PER_GIB)      // Baseline throughput is 3 IOPS per GiB / 4, well past the 170GiB tier switch
+}
+
+/// Whether a gp2 volume of this size is at the 16000 IOPS cap, so growing it adds no IOPS.
+pub const fn gp2_reaches_iops_cap(volume_size_gb: u32) -> bool {
+    volume_size_gb.saturating_mul(GP2_IOPS_PER_GIB) >= GP2_MAX_IOPS
+}
+
+/// I/O credit balance a gp2 volume starts with, which is also the most it can accumulate.
+pub const GP2_IO_CREDIT_BALANCE: u32 = 5_400_000;
+
+/// How long a gp2 volume can sustain its burst IOPS starting from a full credit balance.
+/// Volumes whose baseline already meets the burst IOPS don't depend on credits and get 0.
+pub fn gp2_burst_duration_seconds(volume_size_gb: u32) -> Result<u32, VolumeLimitError> {
+    let limit = calculate_gp2_limits(volume_size_gb)?;
+    if limit.iops >= limit.burst_iops {
+        return Ok(0);
+    }
+    Ok(GP2_IO_CREDIT_BALANCE / (limit.burst_iops - limit.iops))      // Credits are spent at the rate burst exceeds baseline
+}
+
+/// I/O credits per second a gp2 volume earns, which equals its baseline IOPS.
+pub fn gp2_credit_fill_rate(volume_size_gb: u32) -> Result<u32, VolumeLimitError> {
+    Ok(calculate_gp2_limits(volume_size_gb)?.iops)
+}
+
+/// How many IOPS and MiB/s the gp2 burst adds on top of the baseline, zero when bursting gains nothing.
+pub fn gp2_burst_advantage(volume_size_gb: u32) -> Result<(u32, u32), VolumeLimitError> {
+    let limit = calculate_gp2_limits(volume_size_gb)?;
+    Ok((limit.burst_iops.saturating_sub(limit.iops), limit.burst_speed.saturating_sub(limit.speed)))
+}
+
+pub fn calculate_gp3_limits(volume_size_gb: u32, volume_provisioned_iops: Option<u32>, volume_provisioned_throughput: Option<u32>) -> Result<Limit, VolumeLimitError> {
+    calculate_gp3_limits_with_bound(volume_size_gb, volume_provisioned_iops, volume_provisioned_throughput, true)
+}
+
+/// IOPS bounds used to validate provisioned gp3 IOPS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gp3Limits;
+
+impl Gp3Limits {
+    /// Maximum provisioned IOPS AWS currently accepts for gp3, this is the accurate bound.
+    pub const STRICT_MAX_IOPS: u32 = GP3_MAX_IOPS;
+    /// Looser bound older versions of this crate referred to, for callers that relied on it.
+    pub const LEGACY_MAX_IOPS: u32 = 64000;
+}
+
+/// Same as `calculate_gp3_limits`, but with `strict` unset provisioned IOPS are only bounded by
+/// `Gp3Limits::LEGACY_MAX_IOPS` instead of `Gp3Limits::STRICT_MAX_IOPS`.
+pub fn calculate_gp3_limits_with_bound(volume_size_gb: u32, volume_provisioned_iops: Option<u32>, volume_provisioned_throughput: Option<u32>, strict: bool) -> Result<Limit, VolumeLimitError> {
+    let max_iops = if strict { Gp3Limits::STRICT_MAX_IOPS } else { Gp3Limits::LEGACY_MAX_IOPS };
+    let (volume_iops, volume_throughput) = validate_gp3(volume_size_gb, volume_provisioned_iops, volume_provisioned_throughput, max_iops)?;
+    Ok(Limit { iops: volume_iops, speed: volume_throughput, burst_iops: 0, burst_speed: 0 })
+}
+
+// Validation shared by `calculate_gp3_limits` and `is_valid_gp3`, returns the effective IOPS and throughput.
+fn validate_gp3(volume_size_gb: u32, volume_provisioned_iops: Option<u32>, volume_provisioned_throughput: Option<u32>, max_iops: u32) -> Result<(u32, u32), VolumeLimitError> {
+    if !(GP3_MIN_SIZE_GB..=GP3_MAX_SIZE_GB).contains(&volume_size_gb) {
+        return Err(VolumeLimitError::SizeOutOfRange { min: GP3_MIN_SIZE_GB, max: GP3_MAX_SIZE_GB, got: volume_size_gb });
+    }
+
+    let volume_iops = match volume_provisioned_iops {
+        // Set IOPS as Baseline(3000) for gp3 volume if it was created using CLI without provisioned IOPS .
+        None => GP3_BASELINE_IOPS,
+        Some(iops) => {
+            if iops < GP3_BASELINE_IOPS {
+                return Err(VolumeLimitError::IopsBelowBaseline { baseline: GP3_BASELINE_IOPS, got: iops });
+            }
+            if iops > max_iops {                                       // gp3 volumes can be provisioned with up to 16000 IOPS
+                return Err(VolumeLimitError::IopsOutOfRange { min: GP3_BASELINE_IOPS, max: max_iops, got: iops });
+            }
+
+            if iops > volume_size_gb.saturating_mul(GP3_MAX_IOPS_PER_GIB) {    // Compare without division so fractional ratios aren't truncated
+                return Err(VolumeLimitError::IopsToSizeRatioExceeded { max_ratio: GP3_MAX_IOPS_PER_GIB, iops, size: volume_size_gb });
+            }
+
+            iops
         }
-        if volume_iops / throughput < 4 {
-            return Err("Maximum ratio of 0.25:1 is permitted between Throughput (MiBps) and IOPS for Gp3 volume type.")?;
+    };
+
+    let volume_throughput = match volume_provisioned_throughput {
+        // Set Throughput as Baseline(125MiB/s) for gp3 volume if it was created using CLI without provisioned Throughput .
+        None => GP3_BASELINE_THROUGHPUT,
+        Some(throughput) => {
+            if !(GP3_BASELINE_THROUGHPUT..=GP3_MAX_THROUGHPUT).contains(&throughput) {
+                return Err(VolumeLimitError::ThroughputOutOfRange { min: GP3_BASELINE_THROUGHPUT, max: GP3_MAX_THROUGHPUT, got: throughput });
+            }
+            if throughput * GP3_IOPS_PER_MIBPS > volume_iops {         // At most 0.25MiB/s per IOPS, compared without division
+                return Err(VolumeLimitError::ThroughputToIopsRatioExceeded { throughput, iops: volume_iops, min_iops: throughput * GP3_IOPS_PER_MIBPS });
+            }
+            throughput
         }
-        throughput
     };
-    return Ok(Limit { iops: volume_iops, speed: volume_throughput, burst_iops: 0, burst_speed: 0 });
+    Ok((volume_iops, volume_throughput))
+}
+
+/// Builder for gp3 limits, so only the provisioned values that matter need to be set.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Gp3Builder {
+    size_gb: u32,
+    iops: Option<u32>,
+    throughput: Option<u32>,
+    auto_scale_iops: bool,
 }
 
-pub fn calculate_io_limits(volume_provisioned_iops: u32) -> Result<Limit, Box<dyn Error>> {
-    if (volume_provisioned_iops < 100) || (volume_provisioned_iops > 64000) {
-        return Err("Provisioned IOPS can not be less than 100 or greater than 64000.")?;
+impl Gp3Builder {
+    pub fn new() -> Self {
+        Self::default()
     }
 
-    let baseline_throughput;
-    if volume_provisioned_iops < 32000 {
-        let max_available_throughput = 500;                            // io1/io2 Volumes with less than equal to 32000 provisioned IOPS can achieve 500MiB/s of throughput at max.
-        let calculate_tp = volume_provisioned_iops / 4;
-        baseline_throughput = min(max_available_throughput, calculate_tp);
-    } else {
-        let max_available_throughput = 1000;
-        let calculate_tp = volume_provisioned_iops / 64;    // io1/io2 volume provisioned with more than 32,000 IOPS supports a maximum I/O size of 16 KiB
-        baseline_throughput = min(max_available_throughput, calculate_tp);
+    pub fn size(mut self, gb: u32) -> Self {
+        self.size_gb = gb;
+        self
+    }
+
+    pub fn iops(mut self, iops: u32) -> Self {
+        self.iops = Some(iops);
+        self
+    }
+
+    pub fn throughput(mut self, mibps: u32) -> Self {
+        self.throughput = Some(mibps);
+        self
+    }
+
+    /// When IOPS aren't set, raise them above the baseline as far as the throughput requires.
+    pub fn auto_scale_iops(mut self, enabled: bool) -> Self {
+        self.auto_scale_iops = enabled;
+        self
+    }
+
+    pub fn build(self) -> Result<Limit, VolumeLimitError> {
+        let iops = match (self.iops, self.throughput) {
+            (None, Some(throughput)) if self.auto_scale_iops && throughput.saturating_mul(GP3_IOPS_PER_MIBPS) > GP3_BASELINE_IOPS => Some(throughput.saturating_mul(GP3_IOPS_PER_MIBPS)),
+            (iops, _) => iops,
+        };
+        calculate_gp3_limits(self.size_gb, iops, self.throughput)
     }
-    return Ok(Limit { iops: volume_provisioned_iops, speed: baseline_throughput, burst_iops: 0, burst_speed: 0 });
 }
 
-// todo: calculate_st1_limits;
-// https://github.com/awslabs/aws-support-tools/blob/master/EBS/VolumeLimitCalculator/volume_Limit_calculator.sh#L194
+/// Highest IOPS and throughput a gp3 volume of this size can be provisioned with.
+pub fn gp3_max_limit(volume_size_gb: u32) -> Result<Limit, VolumeLimitError> {
+    let iops = volume_size_gb.saturating_mul(GP3_MAX_IOPS_PER_GIB).clamp(GP3_BASELINE_IOPS, GP3_MAX_IOPS);     // 500 IOPS per GiB, up to 16000
+    let provisioned_iops = if iops > GP3_BASELINE_IOPS { Some(iops) } else { None };
+    calculate_gp3_limits(volume_size_gb, provisioned_iops, Some(gp3_max_throughput_for_iops(iops)))
+}
 
-// todo: calculate_sc1_limits
-// https://github.com/awslabs/aws-support-tools/blob/master/EBS/VolumeLimitCalculator/volume_Limit_calculator.sh#L236
+/// Highest gp3 throughput in MiB/s the 0.25:1 throughput to IOPS ratio allows for `iops`.
+pub const fn gp3_max_throughput_for_iops(iops: u32) -> u32 {
+    min(GP3_MAX_THROUGHPUT, iops / GP3_IOPS_PER_MIBPS)      // 0.25MiB/s per IOPS, up to 1000MiB/s
+}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+/// Previous generation magnetic volumes, their performance doesn't depend on the size.
+pub fn calculate_standard_limits(volume_size_gb: u32) -> Result<Limit, VolumeLimitError> {
+    if !(STANDARD_MIN_SIZE_GB..=STANDARD_MAX_SIZE_GB).contains(&volume_size_gb) {
+        return Err(VolumeLimitError::SizeOutOfRange { min: STANDARD_MIN_SIZE_GB, max: STANDARD_MAX_SIZE_GB, got: volume_size_gb });
+    }
 
-    #[test]
-    fn test_gp2_1500() {
-        let limit = calculate_gp2_limits(1500).unwrap();
-        assert_eq!(limit.iops, 4500);
-        assert_eq!(limit.speed, 250);
-        assert_eq!(limit.burst_iops, 0);
-        assert_eq!(limit.burst_speed, 0);
+    let baseline_iops = STANDARD_IOPS;                  // Magnetic volumes deliver about 100 IOPS on average
+    let baseline_throughput = STANDARD_THROUGHPUT;
+    let burst_iops = STANDARD_BURST_IOPS;               // and can burst to a few hundred IOPS and 90MiB/s
+    let burst_throughput = STANDARD_BURST_THROUGHPUT;
+    Ok(Limit { iops: baseline_iops, speed: baseline_throughput, burst_iops, burst_speed: burst_throughput })
+}
+
+pub const fn calculate_io1_limits(volume_size_gb: u32, volume_provisioned_iops: u32) -> Result<Limit, VolumeLimitError> {
+    let limit = match io_limits(volume_provisioned_iops) {
+        Ok(limit) => limit,
+        Err(error) => return Err(error),
+    };
+    if volume_provisioned_iops > volume_size_gb.saturating_mul(IO1_MAX_IOPS_PER_GIB) {   // io1 volumes permit a maximum ratio of 50:1 between IOPS and volume size
+        return Err(VolumeLimitError::IopsToSizeRatioExceeded { max_ratio: IO1_MAX_IOPS_PER_GIB, iops: volume_provisioned_iops, size: volume_size_gb });
     }
+    Ok(limit)
+}
 
-    #[test]
-    fn test_gp2_20() {
-        let limit = calculate_gp2_limits(20).unwrap();
-        assert_eq!(limit.iops, 100);
-        assert_eq!(limit.speed, 25);
-        assert_eq!(limit.burst_iops, 3000);
-        assert_eq!(limit.burst_speed, 128);
+pub const fn calculate_io2_limits(volume_size_gb: u32, volume_provisioned_iops: u32) -> Result<Limit, VolumeLimitError> {
+    if volume_size_gb < IO_MIN_SIZE_GB || volume_size_gb > IO_MAX_SIZE_GB {       // io2 volumes can be created with 4GiB to 16TiB
+        return Err(VolumeLimitError::SizeOutOfRange { min: IO_MIN_SIZE_GB, max: IO_MAX_SIZE_GB, got: volume_size_gb });
+    }
+    let limit = match io_limits(volume_provisioned_iops) {
+        Ok(limit) => limit,
+        Err(error) => return Err(error),
+    };
+    if volume_provisioned_iops > volume_size_gb.saturating_mul(IO2_MAX_IOPS_PER_GIB) {   // io2 volumes permit a maximum ratio of 500:1 between IOPS and volume size
+        return Err(VolumeLimitError::IopsToSizeRatioExceeded { max_ratio: IO2_MAX_IOPS_PER_GIB, iops: volume_provisioned_iops, size: volume_size_gb });
     }
+    Ok(limit)
+}
 
-    #[test]
-    fn test_gp2_1000() {
-        let limit = calculate_gp2_limits(1000).unwrap();
-        assert_eq!(limit.iops, 3000);
-        assert_eq!(limit.speed, 250);
-        assert_eq!(limit.burst_iops, 3000);
-        assert_eq!(limit.burst_speed, 250);
+/// Sized replacement for `calculate_io_limits`, validating the io2 ratio when `io2` is set and the io1 ratio otherwise.
+pub const fn calculate_io_limits_sized(volume_size_gb: u32, volume_provisioned_iops: u32, io2: bool) -> Result<Limit, VolumeLimitError> {
+    if io2 {
+        calculate_io2_limits(volume_size_gb, volume_provisioned_iops)
+    } else {
+        calculate_io1_limits(volume_size_gb, volume_provisioned_iops)
     }
+}
 
-    #[test]
-    fn test_gp2_10000() {
-        let limit = calculate_gp2_limits(10000).unwrap();
-        assert_eq!(limit.iops, 16000);
-        assert_eq!(limit.speed, 250);
-        assert_eq!(limit.burst_iops, 0);
-        assert_eq!(limit.burst_speed, 0);
+#[deprecated(note = "use calculate_io1_limits, calculate_io2_limits or calculate_io_limits_sized, which also validate the IOPS to size ratio")]
+pub const fn calculate_io_limits(volume_provisioned_iops: u32) -> Result<Limit, VolumeLimitError> {
+    io_limits(volume_provisioned_iops)
+}
+
+// Throughput model shared by io1 and io2, it only depends on provisioned IOPS.
+const fn io_limits(volume_provisioned_iops: u32) -> Result<Limit, VolumeLimitError> {
+    if volume_provisioned_iops < IO_MIN_IOPS || volume_provisioned_iops > IO1_MAX_IOPS {      // Same as IO2_MAX_IOPS
+        return Err(VolumeLimitError::IopsOutOfRange { min: IO_MIN_IOPS, max: IO1_MAX_IOPS, got: volume_provisioned_iops });
     }
 
-    #[test]
-    fn test_gp2_3000() {
-        let limit = calculate_gp2_limits(3000).unwrap();
-        assert_eq!(limit.iops, 9000);
-        assert_eq!(limit.speed, 250);
-        assert_eq!(limit.burst_iops, 0);
-        assert_eq!(limit.burst_speed, 0);
+    let baseline_throughput = io_throughput(volume_provisioned_iops, RoundingMode::Floor);
+    Ok(Limit { iops: volume_provisioned_iops, speed: baseline_throughput, burst_iops: 0, burst_speed: 0 })
+}
+
+/// How throughput derived from IOPS is rounded to whole MiB/s.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum RoundingMode {
+    /// Round down, which is what every other calculation in this crate does.
+    #[default]
+    Floor,
+    /// Round to the nearest value, halves round up.
+    Nearest,
+    /// Round up.
+    Ceil,
+}
+
+impl RoundingMode {
+    const fn divide(self, dividend: u32, divisor: u32) -> u32 {
+        match self {
+            RoundingMode::Floor => dividend / divisor,
+            RoundingMode::Nearest => (dividend + divisor / 2) / divisor,
+            RoundingMode::Ceil => dividend.div_ceil(divisor),
+        }
     }
+}
 
-    #[test]
-    fn test_gp3_1500() {
-        let limit = calculate_gp3_limits(1500, None, None).unwrap();
-        assert_eq!(limit.iops, 3000);
-        assert_eq!(limit.speed, 125);
-        assert_eq!(limit.burst_iops, 0);
-        assert_eq!(limit.burst_speed, 0);
+// Continuous at 32000 IOPS: below it IOPS / 4 is already past the 500MiB/s cap, and 32000 / 64 is exactly 500MiB/s.
+const fn io_throughput(volume_provisioned_iops: u32, rounding: RoundingMode) -> u32 {
+    if volume_provisioned_iops < IO_HIGH_IOPS_THRESHOLD {
+        let max_available_throughput = IO_LOW_IOPS_MAX_THROUGHPUT;     // io1/io2 Volumes with less than equal to 32000 provisioned IOPS can achieve 500MiB/s of throughput at max.
+        let calculate_tp = rounding.divide(volume_provisioned_iops, IO_IOPS_PER_MIBPS);
+        min(max_available_throughput, calculate_tp)
+    } else {
+        let max_available_throughput = IO1_MAX_THROUGHPUT;
+        let calculate_tp = rounding.divide(volume_provisioned_iops, IO_HIGH_IOPS_PER_MIBPS);    // io1/io2 volume provisioned with more than 32,000 IOPS supports a maximum I/O size of 16 KiB
+        min(max_available_throughput, calculate_tp)
     }
+}
 
-    #[test]
-    fn test_gp3_20() {
-        let limit = calculate_gp3_limits(20, None, None).unwrap();
-        assert_eq!(limit.iops, 3000);
-        assert_eq!(limit.speed, 125);
-        assert_eq!(limit.burst_iops, 0);
-        assert_eq!(limit.burst_speed, 0);
+/// IOPS needed to move `throughput_mibps` with I/Os of `block_size_kib`, 0 for a zero block size.
+pub fn effective_iops_at_block_size(throughput_mibps: u32, block_size_kib: u32) -> u32 {
+    let iops = (u64::from(throughput_mibps) * 1024).checked_div(u64::from(block_size_kib)).unwrap_or(0);
+    u32::try_from(iops).unwrap_or(u32::MAX)
+}
+
+/// Same as the io1/io2 throughput model, with throughput derived from I/Os of `block_size_kib` instead of the
+/// largest block size, still capped at 500MiB/s below 32000 IOPS and 1000MiB/s above. The ratio to size is not validated.
+pub const fn calculate_io_limits_block(volume_provisioned_iops: u32, block_size_kib: u32) -> Result<Limit, VolumeLimitError> {
+    let limit = match io_limits(volume_provisioned_iops) {
+        Ok(limit) => limit,
+        Err(error) => return Err(error),
+    };
+    let max_available_throughput = if volume_provisioned_iops < IO_HIGH_IOPS_THRESHOLD { IO_LOW_IOPS_MAX_THROUGHPUT } else { IO1_MAX_THROUGHPUT };
+    Ok(Limit { speed: min(max_available_throughput, throughput_for_block_size(volume_provisioned_iops, block_size_kib)), ..limit })
+}
+
+/// Whether io1/io2 throughput at `iops` is derived from IOPS and below the 500MiB/s or 1000MiB/s ceiling,
+/// so provisioning more IOPS would also raise throughput.
+pub const fn io_throughput_is_iops_limited(iops: u32) -> bool {
+    if iops < IO_HIGH_IOPS_THRESHOLD { throughput_for_block_size(iops, IO_MAX_BLOCK_KIB) < IO_LOW_IOPS_MAX_THROUGHPUT } else { throughput_for_block_size(iops, IO_HIGH_IOPS_MAX_BLOCK_KIB) < IO1_MAX_THROUGHPUT }
+}
+
+/// Same as `calculate_io_limits_sized`, rounding the throughput derived from IOPS with `rounding`.
+pub const fn calculate_io_limits_with_rounding(volume_size_gb: u32, volume_provisioned_iops: u32, io2: bool, rounding: RoundingMode) -> Result<Limit, VolumeLimitError> {
+    let limit = match calculate_io_limits_sized(volume_size_gb, volume_provisioned_iops, io2) {
+        Ok(limit) => limit,
+        Err(error) => return Err(error),
+    };
+    Ok(Limit { speed: io_throughput(volume_provisioned_iops, rounding), ..limit })
+}
+
+/// io2 Block Express volumes. `volume_size_gb` is optional and only used to validate the 1000:1 IOPS to size ratio.
+pub fn calculate_io2_block_express_limits(volume_provisioned_iops: u32, volume_size_gb: Option<u32>) -> Result<Limit, VolumeLimitError> {
+    if !(IO_MIN_IOPS..=IO2_BLOCK_EXPRESS_MAX_IOPS).contains(&volume_provisioned_iops) {
+        return Err(VolumeLimitError::IopsOutOfRange { min: IO_MIN_IOPS, max: IO2_BLOCK_EXPRESS_MAX_IOPS, got: volume_provisioned_iops });
     }
 
-    #[test]
-    fn test_gp3_1000() {
-        let limit = calculate_gp3_limits(1000, None, None).unwrap();
-        assert_eq!(limit.iops, 3000);
-        assert_eq!(limit.speed, 125);
-        assert_eq!(limit.burst_iops, 0);
-        assert_eq!(limit.burst_speed, 0);
+    if let Some(size) = volume_size_gb {
+        if volume_provisioned_iops > size.saturating_mul(IO2_BLOCK_EXPRESS_MAX_IOPS_PER_GIB) {
+            return Err(VolumeLimitError::IopsToSizeRatioExceeded { max_ratio: IO2_BLOCK_EXPRESS_MAX_IOPS_PER_GIB, iops: volume_provisioned_iops, size });
+        }
     }
 
-    #[test]
-    fn test_gp3_10000() {
-        let limit = calculate_gp3_limits(10000, None, None).unwrap();
-        assert_eq!(limit.iops, 3000);
-        assert_eq!(limit.speed, 125);
-        assert_eq!(limit.burst_iops, 0);
-        assert_eq!(limit.burst_speed, 0);
+    let max_available_throughput = IO2_BLOCK_EXPRESS_MAX_THROUGHPUT;       // io2 Block Express volumes can achieve 4000MiB/s of throughput at max.
+    let calculate_tp = volume_provisioned_iops * IO2_BLOCK_EXPRESS_MIBPS_PER_1000_IOPS / 1000;     // 0.256MiB/s of throughput per provisioned IOPS
+    let baseline_throughput = min(max_available_throughput, calculate_tp);
+    Ok(Limit { iops: volume_provisioned_iops, speed: baseline_throughput, burst_iops: 0, burst_speed: 0 })
+}
+
+pub fn calculate_st1_limits(volume_size_gb: u32) -> Result<Limit, VolumeLimitError> {
+    if !(HDD_MIN_SIZE_GB..=HDD_MAX_SIZE_GB).contains(&volume_size_gb) {
+        return Err(VolumeLimitError::SizeOutOfRange { min: HDD_MIN_SIZE_GB, max: HDD_MAX_SIZE_GB, got: volume_size_gb });
     }
 
-    #[test]
-    fn test_io1_1500() {
-        let limit = calculate_io_limits(1500).unwrap();
-        assert_eq!(limit.iops, 1500);
-        assert_eq!(limit.speed, 375);
-        assert_eq!(limit.burst_iops, 0);
-        assert_eq!(limit.burst_speed, 0);
+    let max_available_throughput = ST1_MAX_THROUGHPUT;                     // Both baseline and burst throughput of st1 are capped at 500MiB/s
+    let calculate_tp = ST1_THROUGHPUT_PER_TIB * volume_size_gb / 1024;     // Baseline throughput is 40MiB/s per TiB
+    let baseline_throughput = min(max_available_throughput, calculate_tp);
+    let calculate_burst_tp = ST1_BURST_THROUGHPUT_PER_TIB * volume_size_gb / 1024;     // Burst throughput is 250MiB/s per TiB
+    let burst_throughput = min(max_available_throughput, calculate_burst_tp);
+    Ok(Limit { iops: 0, speed: baseline_throughput, burst_iops: 0, burst_speed: burst_throughput })
+}
+
+pub fn calculate_sc1_limits(volume_size_gb: u32) -> Result<Limit, VolumeLimitError> {
+    if !(HDD_MIN_SIZE_GB..=HDD_MAX_SIZE_GB).contains(&volume_size_gb) {
+        return Err(VolumeLimitError::SizeOutOfRange { min: HDD_MIN_SIZE_GB, max: HDD_MAX_SIZE_GB, got: volume_size_gb });
     }
 
-    #[test]
-    fn test_io1_20() {
-        assert_eq!(calculate_io_limits(20).is_err(), true);
+    let max_available_throughput = SC1_MAX_THROUGHPUT;                     // Baseline throughput of sc1 is capped at 192MiB/s
+    let max_available_burst_throughput = SC1_MAX_BURST_THROUGHPUT;         // Burst throughput of sc1 is capped at 250MiB/s
+    let calculate_tp = SC1_THROUGHPUT_PER_TIB * volume_size_gb / 1024;     // Baseline throughput is 12MiB/s per TiB
+    let baseline_throughput = min(max_available_throughput, calculate_tp);
+    let calculate_burst_tp = SC1_BURST_THROUGHPUT_PER_TIB * volume_size_gb / 1024;     // Burst throughput is 80MiB/s per TiB
+    let burst_throughput = min(max_available_burst_throughput, calculate_burst_tp);
+    Ok(Limit { iops: 0, speed: baseline_throughput, burst_iops: 0, burst_speed: burst_throughput })
+}
+
+/// Constants of the model each volume type's limits are calculated with, as returned by `formula_params`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct FormulaParams {
+    pub iops_per_gib: u32,                  // Baseline IOPS per GiB for gp2, the maximum IOPS to size ratio for provisioned types
+    pub iops_floor: u32,
+    pub iops_ceiling: u32,
+    pub throughput_ceiling: u32,
+    pub burst_iops_ceiling: u32,
+    pub burst_throughput_ceiling: u32,
+    pub throughput_divisor: Option<u32>,    // IOPS per MiB/s at the largest I/O size, None when throughput scales with size only
+    pub throughput_per_tib: u32,            // Baseline and burst MiB/s per TiB for types whose throughput scales with size
+    pub burst_throughput_per_tib: u32,
+    pub min_size_gb: u32,
+    pub max_size_gb: u32,
+}
+
+/// Formula parameters of `volume_type`, read from the same constants the calculate functions use.
+/// io2 reports the limits of volumes below the Block Express tier, and the io1 size range is the documented one,
+/// `calculate_io1_limits` only validates the IOPS to size ratio.
+pub const fn formula_params(volume_type: VolumeType) -> FormulaParams {
+    match volume_type {
+        VolumeType::Gp2 => FormulaParams {
+            iops_per_gib: GP2_IOPS_PER_GIB, iops_floor: GP2_MIN_IOPS, iops_ceiling: GP2_MAX_IOPS, throughput_ceiling: GP2_MAX_THROUGHPUT,
+            burst_iops_ceiling: GP2_BURST_IOPS, burst_throughput_ceiling: GP2_MAX_THROUGHPUT,
+            throughput_divisor: Some(GP2_IOPS_PER_MIBPS), throughput_per_tib: 0, burst_throughput_per_tib: 0, min_size_gb: GP2_MIN_SIZE_GB, max_size_gb: GP2_MAX_SIZE_GB,
+        },
+        VolumeType::Gp3 => FormulaParams {
+            iops_per_gib: GP3_MAX_IOPS_PER_GIB, iops_floor: GP3_BASELINE_IOPS, iops_ceiling: GP3_MAX_IOPS, throughput_ceiling: GP3_MAX_THROUGHPUT,
+            burst_iops_ceiling: 0, burst_throughput_ceiling: 0,
+            throughput_divisor: Some(GP3_IOPS_PER_MIBPS), throughput_per_tib: 0, burst_throughput_per_tib: 0, min_size_gb: GP3_MIN_SIZE_GB, max_size_gb: GP3_MAX_SIZE_GB,
+        },
+        VolumeType::Io1 => FormulaParams {
+            iops_per_gib: IO1_MAX_IOPS_PER_GIB, iops_floor: IO_MIN_IOPS, iops_ceiling: IO1_MAX_IOPS, throughput_ceiling: IO1_MAX_THROUGHPUT,
+            burst_iops_ceiling: 0, burst_throughput_ceiling: 0,
+            throughput_divisor: Some(IO_IOPS_PER_MIBPS), throughput_per_tib: 0, burst_throughput_per_tib: 0, min_size_gb: IO_MIN_SIZE_GB, max_size_gb: IO_MAX_SIZE_GB,
+        },
+        VolumeType::Io2 => FormulaParams {
+            iops_per_gib: IO2_MAX_IOPS_PER_GIB, iops_floor: IO_MIN_IOPS, iops_ceiling: IO2_MAX_IOPS, throughput_ceiling: IO2_MAX_THROUGHPUT,
+            burst_iops_ceiling: 0, burst_throughput_ceiling: 0,
+            throughput_divisor: Some(IO_IOPS_PER_MIBPS), throughput_per_tib: 0, burst_throughput_per_tib: 0, min_size_gb: IO_MIN_SIZE_GB, max_size_gb: IO_MAX_SIZE_GB,
+        },
+        VolumeType::St1 => FormulaParams {
+            iops_per_gib: 0, iops_floor: 0, iops_ceiling: 0, throughput_ceiling: ST1_MAX_THROUGHPUT,
+            burst_iops_ceiling: 0, burst_throughput_ceiling: ST1_MAX_THROUGHPUT,
+            throughput_divisor: None, throughput_per_tib: ST1_THROUGHPUT_PER_TIB, burst_throughput_per_tib: ST1_BURST_THROUGHPUT_PER_TIB, min_size_gb: HDD_MIN_SIZE_GB, max_size_gb: HDD_MAX_SIZE_GB,
+        },
+        VolumeType::Sc1 => FormulaParams {
+            iops_per_gib: 0, iops_floor: 0, iops_ceiling: 0, throughput_ceiling: SC1_MAX_THROUGHPUT,
+            burst_iops_ceiling: 0, burst_throughput_ceiling: SC1_MAX_BURST_THROUGHPUT,
+            throughput_divisor: None, throughput_per_tib: SC1_THROUGHPUT_PER_TIB, burst_throughput_per_tib: SC1_BURST_THROUGHPUT_PER_TIB, min_size_gb: HDD_MIN_SIZE_GB, max_size_gb: HDD_MAX_SIZE_GB,
+        },
+        VolumeType::Standard => FormulaParams {
+            iops_per_gib: 0, iops_floor: STANDARD_IOPS, iops_ceiling: STANDARD_IOPS, throughput_ceiling: STANDARD_THROUGHPUT,
+            burst_iops_ceiling: STANDARD_BURST_IOPS, burst_throughput_ceiling: STANDARD_BURST_THROUGHPUT,
+            throughput_divisor: None, throughput_per_tib: 0, burst_throughput_per_tib: 0, min_size_gb: STANDARD_MIN_SIZE_GB, max_size_gb: STANDARD_MAX_SIZE_GB,
+        },
     }
+}
 
-    #[test]
-    fn test_io1_1000() {
-        let limit = calculate_io_limits(1000).unwrap();
-        assert_eq!(limit.iops, 1000);
-        assert_eq!(limit.speed, 250);
-        assert_eq!(limit.burst_iops, 0);
-        assert_eq!(limit.burst_speed, 0);
+/// Calculates the limits of any supported volume type.
+///
+/// Only the parameters relevant to `volume_type` are used, the rest are ignored:
+/// - `Gp2`, `St1`, `Sc1`, `Standard`: `volume_size_gb`
+/// - `Gp3`: `volume_size_gb`, `provisioned_iops` and `provisioned_throughput` (both optional)
+/// - `Io1`, `Io2`: `volume_size_gb` and `provisioned_iops` (required)
+pub fn calculate(volume_type: VolumeType, volume_size_gb: u32, provisioned_iops: Option<u32>, provisioned_throughput: Option<u32>) -> Result<Limit, VolumeLimitError> {
+    match volume_type {
+        VolumeType::Gp2 => calculate_gp2_limits(volume_size_gb),
+        VolumeType::Gp3 => calculate_gp3_limits(volume_size_gb, provisioned_iops, provisioned_throughput),
+        VolumeType::Io1 => match provisioned_iops {
+            Some(iops) => calculate_io1_limits(volume_size_gb, iops),
+            None => Err(VolumeLimitError::MissingProvisionedIops),
+        },
+        VolumeType::Io2 => match provisioned_iops {
+            Some(iops) => calculate_io2_limits(volume_size_gb, iops),
+            None => Err(VolumeLimitError::MissingProvisionedIops),
+        },
+        VolumeType::St1 => calculate_st1_limits(volume_size_gb),
+        VolumeType::Sc1 => calculate_sc1_limits(volume_size_gb),
+        VolumeType::Standard => calculate_standard_limits(volume_size_gb),
     }
+}
 
-    #[test]
-    fn test_io1_10000() {
-        let limit = calculate_io_limits(10000).unwrap();
-        assert_eq!(limit.iops, 10000);
-        assert_eq!(limit.speed, 500);
-        assert_eq!(limit.burst_iops, 0);
+/// Volume size in GiB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SizeGib(pub u32);
+
+/// Provisioned IOPS.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Iops(pub u32);
+
+/// Provisioned throughput in MiB/s.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct ThroughputMib(pub u32);
+
+impl From<u32> for SizeGib {
+    fn from(value: u32) -> Self {
+        SizeGib(value)
+    }
+}
+
+impl From<u32> for Iops {
+    fn from(value: u32) -> Self {
+        Iops(value)
+    }
+}
+
+impl From<u32> for ThroughputMib {
+    fn from(value: u32) -> Self {
+        ThroughputMib(value)
+    }
+}
+
+/// Same as `calculate`, with each argument in its own type so they can't be swapped by accident.
+pub fn calculate_typed(volume_type: VolumeType, size: SizeGib, provisioned_iops: Option<Iops>, provisioned_throughput: Option<ThroughputMib>) -> Result<Limit, VolumeLimitError> {
+    calculate(volume_type, size.0, provisioned_iops.map(|iops| iops.0), provisioned_throughput.map(|throughput| throughput.0))
+}
+
+/// Baseline IOPS of a volume without provisioned IOPS or throughput, io1 and io2 fail with `MissingProvisionedIops`.
+pub fn baseline_iops(volume_type: VolumeType, volume_size_gb: u32) -> Result<u32, VolumeLimitError> {
+    Ok(calculate(volume_type, volume_size_gb, None, None)?.iops)
+}
+
+/// Baseline throughput in MiB/s of a volume without provisioned IOPS or throughput, io1 and io2 fail with `MissingProvisionedIops`.
+pub fn baseline_throughput(volume_type: VolumeType, volume_size_gb: u32) -> Result<u32, VolumeLimitError> {
+    Ok(calculate(volume_type, volume_size_gb, None, None)?.speed)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SizeUnit {
+    GiB,
+    TiB,
+}
+
+impl SizeUnit {
+    /// Converts `size` expressed in this unit to GiB (1 TiB = 1024 GiB), rounded to the nearest whole GiB.
+    pub fn to_gib(self, size: f64) -> u32 {
+        let size_gb = match self {
+            SizeUnit::GiB => size,
+            SizeUnit::TiB => size * 1024.0,
+        };
+        // Rounds half up without f64::round, which isn't available in no_std builds. The cast saturates,
+        // so negative or NaN sizes become 0 and are rejected by the range checks.
+        (size_gb + 0.5) as u32
+    }
+}
+
+/// Same as `calculate`, with the volume size given in `unit`.
+pub fn calculate_with_unit(volume_type: VolumeType, volume_size: f64, unit: SizeUnit, provisioned_iops: Option<u32>, provisioned_throughput: Option<u32>) -> Result<Limit, VolumeLimitError> {
+    calculate(volume_type, unit.to_gib(volume_size), provisioned_iops, provisioned_throughput)
+}
+
+/// Parses a size such as `"500"`, `"500GiB"`, `"1.5TiB"` or `"2TB"` into whole GiB.
+/// Units are case-insensitive and default to GiB, GB and TB are decimal. Fractions of a GiB are rounded up,
+/// so the result is never smaller than the requested size.
+pub fn parse_size_to_gib(size: &str) -> Result<u32, VolumeLimitError> {
+    let size = size.trim();
+    let (number, bytes_per_unit) = [("GiB", 1_073_741_824.0), ("TiB", 1_099_511_627_776.0), ("GB", 1e9), ("TB", 1e12)]
+        .into_iter()
+        .find_map(|(suffix, bytes)| {
+            let split = size.len().checked_sub(suffix.len())?;
+            let unit = size.get(split..)?;
+            unit.eq_ignore_ascii_case(suffix).then(|| (&size[..split], bytes))
+        })
+        .unwrap_or((size, 1_073_741_824.0));
+    let value: f64 = number.trim_end().parse().map_err(|_| VolumeLimitError::UnparseableSize)?;
+    if !value.is_finite() || value < 0.0 {
+        return Err(VolumeLimitError::UnparseableSize);
+    }
+    let size_gib = value * bytes_per_unit / 1_073_741_824.0;
+    if size_gib > f64::from(u32::MAX) {
+        return Err(VolumeLimitError::Overflow);
+    }
+    // Rounds up without f64::ceil, which isn't available in no_std builds.
+    let whole = size_gib as u32;
+    Ok(if f64::from(whole) < size_gib { whole + 1 } else { whole })
+}
+
+/// Calculates limits from the parameters of an EC2 `CreateVolume` request, as typed by the AWS SDK.
+pub fn from_create_volume_request(volume_type: &str, size: i32, iops: Option<i32>, throughput: Option<i32>) -> Result<Limit, VolumeLimitError> {
+    let volume_type = volume_type.parse::<VolumeType>()?;
+    let size = non_negative("Size", size)?;
+    let iops = match iops {
+        Some(iops) => Some(non_negative("Iops", iops)?),
+        None => None,
+    };
+    let throughput = match throughput {
+        Some(throughput) => Some(non_negative("Throughput", throughput)?),
+        None => None,
+    };
+    calculate(volume_type, size, iops, throughput)
+}
+
+pub(crate) fn non_negative(name: &'static str, value: i32) -> Result<u32, VolumeLimitError> {
+    u32::try_from(value).map_err(|_| VolumeLimitError::NegativeParameter { name, got: value })
+}
+
+// Volume types from the cheapest to the most expensive, roughly by price per GiB.
+pub(crate) const COST_ORDER: [VolumeType; 6] = [VolumeType::Sc1, VolumeType::St1, VolumeType::Gp2, VolumeType::Gp3, VolumeType::Io1, VolumeType::Io2];
+
+// Best baseline a volume of the given type and size can deliver, provisioning gp3 and io1/io2 to their maximum.
+fn max_capability(volume_type: VolumeType, volume_size_gb: u32) -> Result<Limit, VolumeLimitError> {
+    match volume_type {
+        VolumeType::Gp3 => gp3_max_limit(volume_size_gb),
+        VolumeType::Io1 => calculate_io1_limits(volume_size_gb, volume_size_gb.saturating_mul(IO1_MAX_IOPS_PER_GIB).clamp(IO_MIN_IOPS, IO1_MAX_IOPS)),
+        VolumeType::Io2 => calculate_io2_limits(volume_size_gb, volume_size_gb.saturating_mul(IO2_MAX_IOPS_PER_GIB).clamp(IO_MIN_IOPS, IO2_MAX_IOPS)),
+        _ => calculate(volume_type, volume_size_gb, None, None),
+    }
+}
+
+/// Cheapest volume type whose baseline at `size_gb` satisfies both targets.
+///
+/// Types are tried in the order sc1, st1, gp2, gp3, io1, io2, with gp3 and io1/io2
+/// considered at the highest IOPS and throughput they can be provisioned to.
+pub fn recommend_volume_type(target_iops: u32, target_mibps: u32, size_gb: u32) -> Result<VolumeType, VolumeLimitError> {
+    COST_ORDER.into_iter()
+        .find(|&volume_type| can_satisfy(volume_type, target_iops, target_mibps, size_gb))
+        .ok_or(VolumeLimitError::NoEligibleVolumeType)
+}
+
+/// Every volume type able to reach both targets at `size_gb`, cheapest first.
+pub fn eligible_volume_types(target_iops: u32, target_mibps: u32, size_gb: u32) -> Vec<VolumeType> {
+    COST_ORDER.into_iter()
+        .filter(|&volume_type| can_satisfy(volume_type, target_iops, target_mibps, size_gb))
+        .collect()
+}
+
+pub(crate) fn can_satisfy(volume_type: VolumeType, target_iops: u32, target_mibps: u32, size_gb: u32) -> bool {
+    match max_capability(volume_type, size_gb) {
+        Ok(limit) => limit.iops >= target_iops && limit.speed >= target_mibps,
+        Err(_) => false,
+    }
+}
+
+/// Smallest gp2 volume size whose baseline IOPS meets `target_iops`.
+pub fn min_gp2_size_for_iops(target_iops: u32) -> Result<u32, VolumeLimitError> {
+    if target_iops > GP2_MAX_IOPS {
+        return Err(VolumeLimitError::IopsTargetUnreachable { max: GP2_MAX_IOPS, target: target_iops });
+    }
+
+    if target_iops <= GP2_MIN_IOPS {       // Every gp2 volume gets at least 100 IOPS
+        return Ok(GP2_MIN_SIZE_GB);
+    }
+    Ok(target_iops.div_ceil(GP2_IOPS_PER_GIB))            // Above the floor baseline grows by 3 IOPS per GiB
+}
+
+/// Smallest gp2 volume size whose baseline throughput meets `target_mibps`.
+pub fn min_gp2_size_for_throughput(target_mibps: u32) -> Result<u32, VolumeLimitError> {
+    if target_mibps > GP2_MAX_THROUGHPUT {
+        return Err(VolumeLimitError::ThroughputTargetUnreachable { max: GP2_MAX_THROUGHPUT, target: target_mibps });
+    }
+
+    if target_mibps <= GP2_MIN_IOPS / GP2_IOPS_PER_MIBPS {     // The 100 IOPS floor already gives 25MiB/s
+        return Ok(GP2_MIN_SIZE_GB);
+    }
+    // Throughput is IOPS / 4 on both sides of the tier boundary and the lower tier cap is never reached,
+    // see `GP2_SMALL_TIER_MAX_SIZE_GB`, so solve 3 * size / 4 >= target.
+    Ok((GP2_IOPS_PER_MIBPS * target_mibps).div_ceil(GP2_IOPS_PER_GIB))
+}
+
+/// Smallest io1/io2 provisioned IOPS whose baseline throughput meets `target_mibps`.
+pub fn min_io_iops_for_throughput(target_mibps: u32) -> Result<u32, VolumeLimitError> {
+    if target_mibps > IO1_MAX_THROUGHPUT {
+        return Err(VolumeLimitError::ThroughputTargetUnreachable { max: IO1_MAX_THROUGHPUT, target: target_mibps });
+    }
+
+    if target_mibps <= IO_LOW_IOPS_MAX_THROUGHPUT {
+        Ok(max(IO_MIN_IOPS, target_mibps * IO_IOPS_PER_MIBPS))                       // IOPS / 4 below 32000 IOPS, never below the 100 IOPS minimum
+    } else {
+        Ok(max(IO_HIGH_IOPS_THRESHOLD, target_mibps * IO_HIGH_IOPS_PER_MIBPS))      // Past 500MiB/s only IOPS / 64 from 32000 IOPS on can deliver
+    }
+}
+
+/// Smallest gp3 volume size that can be provisioned with `iops` under the 500 IOPS per GiB ratio.
+pub fn gp3_min_size_for_iops(iops: u32) -> Result<u32, VolumeLimitError> {
+    if iops < GP3_BASELINE_IOPS {
+        return Err(VolumeLimitError::IopsBelowBaseline { baseline: GP3_BASELINE_IOPS, got: iops });
+    }
+    if iops > GP3_MAX_IOPS {
+        return Err(VolumeLimitError::IopsOutOfRange { min: GP3_BASELINE_IOPS, max: GP3_MAX_IOPS, got: iops });
+    }
+
+    Ok(iops.div_ceil(GP3_MAX_IOPS_PER_GIB))
+}
+
+/// Limits of a volume provisioned with the minimum IOPS its type requires.
+fn default_provisioned_limits(volume_type: VolumeType, volume_size_gb: u32) -> Result<Limit, VolumeLimitError> {
+    match volume_type {
+        VolumeType::Io1 | VolumeType::Io2 => calculate(volume_type, volume_size_gb, Some(IO_MIN_IOPS), None),
+        _ => calculate(volume_type, volume_size_gb, None, None),
+    }
+}
+
+/// Limits of every volume type at `volume_size_gb` with the minimum provisioning, with an error for types that don't support the size.
+pub fn all_limits(volume_size_gb: u32) -> Vec<(VolumeType, Result<Limit, VolumeLimitError>)> {
+    VolumeType::ALL.into_iter()
+        .map(|volume_type| (volume_type, default_provisioned_limits(volume_type, volume_size_gb)))
+        .collect()
+}
+
+/// Whether resizing a volume from `from_gb` to `to_gb` changes its baseline IOPS or throughput.
+pub fn limit_changes_on_resize(volume_type: VolumeType, from_gb: u32, to_gb: u32) -> Result<bool, VolumeLimitError> {
+    let before = default_provisioned_limits(volume_type, from_gb)?;
+    let after = default_provisioned_limits(volume_type, to_gb)?;
+    Ok(before.iops != after.iops || before.speed != after.speed)
+}
+
+/// Advises against gp3 provisioning of more than 1MiB/s of throughput per GiB, which is valid but unusual for such a small volume.
+pub fn warn_small_volume_high_throughput(size_gb: u32, throughput: u32) -> Option<String> {
+    if throughput <= size_gb {
+        return None;
+    }
+    Some(format!("{}MiB/s of throughput on a {}GiB volume is more than 1MiB/s per GiB, check that the volume isn't undersized.", throughput, size_gb))
+}
+
+/// Checks that a `from` volume of `size_gb` can be modified to the `to` type without resizing, and returns
+/// the limits after the change with the least provisioning the new type allows.
+pub fn validate_type_change(from: VolumeType, to: VolumeType, size_gb: u32) -> Result<Limit, VolumeLimitError> {
+    default_provisioned_limits(from, size_gb)?;
+    default_provisioned_limits(to, size_gb)
+}
+
+/// Fractions of the baseline IOPS and throughput a measured workload uses, above 1.0 the volume relies on burst.
+/// Any use of a zero baseline is reported as infinite.
+pub fn utilization(limit: &Limit, actual_iops: u32, actual_mibps: u32) -> (f64, f64) {
+    (fraction(actual_iops, limit.iops), fraction(actual_mibps, limit.speed))
+}
+
+fn fraction(actual: u32, baseline: u32) -> f64 {
+    match (actual, baseline) {
+        (0, _) => 0.0,
+        (_, 0) => f64::INFINITY,
+        _ => f64::from(actual) / f64::from(baseline),
+    }
+}
+
+/// gp3 IOPS and throughput matching the baseline of a gp2 volume of the same size, never below the gp3 baseline.
+pub fn gp3_equivalent_of_gp2(size_gb: u32) -> Result<(u32, u32), VolumeLimitError> {
+    let gp2 = calculate_gp2_limits(size_gb)?;
+    // Baselines are left unprovisioned, 3000 IOPS would exceed the 500:1 ratio below 6GiB
+    let iops = if gp2.iops > GP3_BASELINE_IOPS { Some(gp2.iops) } else { None };
+    let throughput = if gp2.speed > GP3_BASELINE_THROUGHPUT { Some(gp2.speed) } else { None };
+    let gp3 = calculate_gp3_limits(size_gb, iops, throughput)?;
+    Ok((gp3.iops, gp3.speed))
+}
+
+/// Suggests gp2 when its baseline at the same size already delivers the requested gp3 IOPS and throughput.
+pub fn suggest_downgrade_to_gp2(size_gb: u32, gp3_iops: u32, gp3_throughput: u32) -> Option<String> {
+    let gp2 = calculate_gp2_limits(size_gb).ok()?;
+    if gp2.iops >= gp3_iops && gp2.speed >= gp3_throughput {
+        Some(format!(
+            "gp2 at {}GiB provides {} IOPS and {} MiB/s, which meets the requested {} IOPS and {} MiB/s without provisioning gp3.",
+            size_gb, gp2.iops, gp2.speed, gp3_iops, gp3_throughput
+        ))
+    } else {
+        None
+    }
+}
+
+/// Explains how a volume of this type performs right after it is restored from a snapshot.
+pub fn restore_performance_note(volume_type: VolumeType) -> Option<&'static str> {
+    match volume_type {
+        VolumeType::Gp2 | VolumeType::Gp3 | VolumeType::Io1 | VolumeType::Io2 =>
+            Some("Blocks restored from a snapshot are loaded from S3 on first access, so the first read of each block has much higher latency than the provisioned IOPS suggest. Read every block once or enable fast snapshot restore to get full performance immediately."),
+        VolumeType::St1 | VolumeType::Sc1 =>
+            Some("Blocks restored from a snapshot are loaded from S3 on first access, so sequential throughput stays well below the baseline until every block has been read once."),
+        VolumeType::Standard =>
+            Some("Blocks restored from a snapshot are loaded from S3 on first access, which adds latency to the first read of each block."),
+    }
+}
+
+/// Whether `calculate_gp2_limits` accepts this size.
+pub const fn is_valid_gp2(volume_size_gb: u32) -> bool {
+    calculate_gp2_limits(volume_size_gb).is_ok()
+}
+
+/// Whether `calculate_gp3_limits` accepts these parameters, without building a `Limit`.
+pub fn is_valid_gp3(volume_size_gb: u32, volume_provisioned_iops: Option<u32>, volume_provisioned_throughput: Option<u32>) -> bool {
+    validate_gp3(volume_size_gb, volume_provisioned_iops, volume_provisioned_throughput, Gp3Limits::STRICT_MAX_IOPS).is_ok()
+}
+
+/// Whether `calculate_io1_limits` accepts these parameters.
+pub const fn is_valid_io1(volume_size_gb: u32, volume_provisioned_iops: u32) -> bool {
+    calculate_io1_limits(volume_size_gb, volume_provisioned_iops).is_ok()
+}
+
+/// Whether `calculate_io2_limits` accepts these parameters.
+pub const fn is_valid_io2(volume_size_gb: u32, volume_provisioned_iops: u32) -> bool {
+    calculate_io2_limits(volume_size_gb, volume_provisioned_iops).is_ok()
+}
+
+/// Whether `calculate_st1_limits` accepts this size.
+pub fn is_valid_st1(volume_size_gb: u32) -> bool {
+    calculate_st1_limits(volume_size_gb).is_ok()
+}
+
+/// Whether `calculate_sc1_limits` accepts this size.
+pub fn is_valid_sc1(volume_size_gb: u32) -> bool {
+    calculate_sc1_limits(volume_size_gb).is_ok()
+}
+
+/// Whether `calculate_standard_limits` accepts this size.
+pub fn is_valid_standard(volume_size_gb: u32) -> bool {
+    calculate_standard_limits(volume_size_gb).is_ok()
+}
+
+/// Describes in prose the limits of a volume and which rule produced them.
+pub fn explain(volume_type: VolumeType, size_gb: u32, provisioned_iops: Option<u32>, provisioned_throughput: Option<u32>) -> Result<String, VolumeLimitError> {
+    let limit = calculate(volume_type, size_gb, provisioned_iops, provisioned_throughput)?;
+    let text = match volume_type {
+        VolumeType::Gp2 => {
+            let detailed = calculate_gp2_limits_detailed(size_gb)?;
+            let iops_rule = if GP2_IOPS_PER_GIB * size_gb < GP2_MIN_IOPS {
+                format!("the {} IOPS minimum", GP2_MIN_IOPS)
+            } else if detailed.binding_constraint == BindingConstraint::IopsCeiling {
+                format!("capped at the {} IOPS maximum", GP2_MAX_IOPS)
+            } else {
+                format!("{} IOPS per GiB", GP2_IOPS_PER_GIB)
+            };
+            let throughput_rule = if detailed.binding_constraint == BindingConstraint::IopsToThroughputConversion {
+                format!("converted from IOPS at {}KiB per I/O", GP2_MAX_BLOCK_KIB)
+            } else {
+                format!("capped at the {} MiB/s maximum", GP2_MAX_THROUGHPUT)
+            };
+            let mut text = format!("A {} GiB gp2 volume gets {} baseline IOPS ({}), {} MiB/s throughput ({})", size_gb, limit.iops, iops_rule, limit.speed, throughput_rule);
+            if limit.burst_iops > 0 {
+                text += &format!(", and can burst to {} IOPS and {} MiB/s", limit.burst_iops, limit.burst_speed);
+            }
+            text + "."
+        }
+        VolumeType::Gp3 => {
+            let iops_rule = if provisioned_iops.is_some() { "provisioned" } else { "the included baseline" };
+            let throughput_rule = if provisioned_throughput.is_some() { "provisioned" } else { "the included baseline" };
+            format!("A {} GiB gp3 volume gets {} IOPS ({}) and {} MiB/s throughput ({}), independent of its size.", size_gb, limit.iops, iops_rule, limit.speed, throughput_rule)
+        }
+        VolumeType::Io1 | VolumeType::Io2 => {
+            let throughput_rule = if io_throughput_is_iops_limited(limit.iops) { "converted from the provisioned IOPS" } else { "capped at the maximum for this IOPS level" };
+            format!("A {} GiB {} volume gets its {} provisioned IOPS and {} MiB/s throughput ({}).", size_gb, volume_type, limit.iops, limit.speed, throughput_rule)
+        }
+        VolumeType::St1 | VolumeType::Sc1 =>
+            format!("A {} GiB {} volume gets {} MiB/s baseline throughput and can burst to {} MiB/s, both scaled by size up to the maximum for the type.", size_gb, volume_type, limit.speed, limit.burst_speed),
+        VolumeType::Standard =>
+            format!("A {} GiB standard volume gets {} IOPS and {} MiB/s throughput regardless of size, and can burst to {} IOPS and {} MiB/s.", size_gb, limit.iops, limit.speed, limit.burst_iops, limit.burst_speed),
+    };
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_gp2_1500() {
+        let limit = calculate_gp2_limits(1500).unwrap();
+        assert_eq!(limit.iops, 4500);
+        assert_eq!(limit.speed, 250);
+        assert_eq!(limit.burst_iops, 0);
+        assert_eq!(limit.burst_speed, 0);
+    }
+
+    #[test]
+    fn test_gp2_20() {
+        let limit = calculate_gp2_limits(20).unwrap();
+        assert_eq!(limit.iops, 100);
+        assert_eq!(limit.speed, 25);
+        assert_eq!(limit.burst_iops, 3000);
+        assert_eq!(limit.burst_speed, 128);
+    }
+
+    #[test]
+    fn test_gp2_1000() {
+        let limit = calculate_gp2_limits(1000).unwrap();
+        assert_eq!(limit.iops, 3000);
+        assert_eq!(limit.speed, 250);
+        assert_eq!(limit.burst_iops, 3000);
+        assert_eq!(limit.burst_speed, 250);
+    }
+
+    #[test]
+    fn test_gp2_10000() {
+        let limit = calculate_gp2_limits(10000).unwrap();
+        assert_eq!(limit.iops, 16000);
+        assert_eq!(limit.speed, 250);
+        assert_eq!(limit.burst_iops, 0);
+        assert_eq!(limit.burst_speed, 0);
+    }
+
+    #[test]
+    fn test_gp2_3000() {
+        let limit = calculate_gp2_limits(3000).unwrap();
+        assert_eq!(limit.iops, 9000);
+        assert_eq!(limit.speed, 250);
+        assert_eq!(limit.burst_iops, 0);
+        assert_eq!(limit.burst_speed, 0);
+    }
+
+    #[test]
+    fn test_gp2_1() {
+        let limit = calculate_gp2_limits(1).unwrap();
+        assert_eq!(limit.iops, 100);
+        assert_eq!(limit.speed, 25);
+        assert_eq!(limit.burst_iops, 3000);
+        assert_eq!(limit.burst_speed, 128);
+    }
+
+    #[test]
+    fn test_gp2_33() {
+        let limit = calculate_gp2_limits(33).unwrap();        // 3 * 33 = 99, still on the 100 IOPS floor
+        assert_eq!(limit.iops, 100);
+        assert_eq!(limit.speed, 25);
+        assert_eq!(limit.burst_iops, 3000);
+        assert_eq!(limit.burst_speed, 128);
+    }
+
+    #[test]
+    fn test_gp2_34() {
+        let limit = calculate_gp2_limits(34).unwrap();        // 3 * 34 = 102, first size above the floor
+        assert_eq!(limit.iops, 102);
+        assert_eq!(limit.speed, 25);
+        assert_eq!(limit.burst_iops, 3000);
+        assert_eq!(limit.burst_speed, 128);
+    }
+
+    #[test]
+    fn test_gp3_1500() {
+        let limit = calculate_gp3_limits(1500, None, None).unwrap();
+        assert_eq!(limit.iops, 3000);
+        assert_eq!(limit.speed, 125);
+        assert_eq!(limit.burst_iops, 0);
+        assert_eq!(limit.burst_speed, 0);
+    }
+
+    #[test]
+    fn test_gp3_20() {
+        let limit = calculate_gp3_limits(20, None, None).unwrap();
+        assert_eq!(limit.iops, 3000);
+        assert_eq!(limit.speed, 125);
+        assert_eq!(limit.burst_iops, 0);
+        assert_eq!(limit.burst_speed, 0);
+    }
+
+    #[test]
+    fn test_gp3_1000() {
+        let limit = calculate_gp3_limits(1000, None, None).unwrap();
+        assert_eq!(limit.iops, 3000);
+        assert_eq!(limit.speed, 125);
+        assert_eq!(limit.burst_iops, 0);
+        assert_eq!(limit.burst_speed, 0);
+    }
+
+    #[test]
+    fn test_gp3_10000() {
+        let limit = calculate_gp3_limits(10000, None, None).unwrap();
+        assert_eq!(limit.iops, 3000);
+        assert_eq!(limit.speed, 125);
+        assert_eq!(limit.burst_iops, 0);
+        assert_eq!(limit.burst_speed, 0);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_io1_1500() {
+        let limit = calculate_io_limits(1500).unwrap();
+        assert_eq!(limit.iops, 1500);
+        assert_eq!(limit.speed, 375);
+        assert_eq!(limit.burst_iops, 0);
         assert_eq!(limit.burst_speed, 0);
     }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_io1_20() {
+        assert_eq!(calculate_io_limits(20).unwrap_err(), VolumeLimitError::IopsOutOfRange { min: 100, max: 64000, got: 20 });
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_io1_1000() {
+        let limit = calculate_io_limits(1000).unwrap();
+        assert_eq!(limit.iops, 1000);
+        assert_eq!(limit.speed, 250);
+        assert_eq!(limit.burst_iops, 0);
+        assert_eq!(limit.burst_speed, 0);
+    }
+
+    #[test]
+    #[allow(deprecated)]
+    fn test_io1_10000() {
+        let limit = calculate_io_limits(10000).unwrap();
+        assert_eq!(limit.iops, 10000);
+        assert_eq!(limit.speed, 500);
+        assert_eq!(limit.burst_iops, 0);
+        assert_eq!(limit.burst_speed, 0);
+    }
+
+    #[test]
+    fn test_st1_125() {
+        let limit = calculate_st1_limits(125).unwrap();
+        assert_eq!(limit.iops, 0);
+        assert_eq!(limit.speed, 4);
+        assert_eq!(limit.burst_iops, 0);
+        assert_eq!(limit.burst_speed, 30);
+    }
+
+    #[test]
+    fn test_st1_500() {
+        let limit = calculate_st1_limits(500).unwrap();
+        assert_eq!(limit.iops, 0);
+        assert_eq!(limit.speed, 19);
+        assert_eq!(limit.burst_iops, 0);
+        assert_eq!(limit.burst_speed, 122);
+    }
+
+    #[test]
+    fn test_st1_4096() {
+        let limit = calculate_st1_limits(4096).unwrap();
+        assert_eq!(limit.iops, 0);
+        assert_eq!(limit.speed, 160);
+        assert_eq!(limit.burst_iops, 0);
+        assert_eq!(limit.burst_speed, 500);
+    }
+
+    #[test]
+    fn test_st1_out_of_range() {
+        assert_eq!(calculate_st1_limits(124).unwrap_err(), VolumeLimitError::SizeOutOfRange { min: 125, max: 16384, got: 124 });
+        assert!(calculate_st1_limits(16385).is_err());
+    }
+
+    #[test]
+    fn test_sc1_125() {
+        let limit = calculate_sc1_limits(125).unwrap();
+        assert_eq!(limit.iops, 0);
+        assert_eq!(limit.speed, 1);
+        assert_eq!(limit.burst_iops, 0);
+        assert_eq!(limit.burst_speed, 9);
+    }
+
+    #[test]
+    fn test_sc1_1000() {
+        let limit = calculate_sc1_limits(1000).unwrap();
+        assert_eq!(limit.iops, 0);
+        assert_eq!(limit.speed, 11);
+        assert_eq!(limit.burst_iops, 0);
+        assert_eq!(limit.burst_speed, 78);
+    }
+
+    #[test]
+    fn test_sc1_16384() {
+        let limit = calculate_sc1_limits(16384).unwrap();
+        assert_eq!(limit.iops, 0);
+        assert_eq!(limit.speed, 192);
+        assert_eq!(limit.burst_iops, 0);
+        assert_eq!(limit.burst_speed, 250);
+    }
+
+    #[test]
+    fn test_sc1_below_minimum() {
+        assert!(calculate_sc1_limits(124).is_err());
+        assert!(calculate_sc1_limits(0).is_err());
+    }
+
+    #[test]
+    fn test_calculate_dispatch() {
+        let limit = calculate(VolumeType::Gp2, 20, Some(5000), Some(500)).unwrap();
+        assert_eq!(limit.iops, 100);
+        assert_eq!(limit.speed, 25);
+
+        let limit = calculate(VolumeType::Gp3, 1000, Some(6000), Some(500)).unwrap();
+        assert_eq!(limit.iops, 6000);
+        assert_eq!(limit.speed, 500);
+
+        let limit = calculate(VolumeType::Io2, 1000, Some(1500), None).unwrap();
+        assert_eq!(limit.iops, 1500);
+        assert_eq!(limit.speed, 375);
+
+        let limit = calculate(VolumeType::St1, 4096, None, None).unwrap();
+        assert_eq!(limit.speed, 160);
+
+        let limit = calculate(VolumeType::Sc1, 16384, None, None).unwrap();
+        assert_eq!(limit.speed, 192);
+    }
+
+    #[test]
+    fn test_calculate_io_without_iops() {
+        assert_eq!(calculate(VolumeType::Io1, 1000, None, None).unwrap_err(), VolumeLimitError::MissingProvisionedIops);
+        assert!(calculate(VolumeType::Io2, 1000, None, Some(500)).is_err());
+    }
+
+    #[test]
+    fn test_gp3_errors() {
+        assert_eq!(calculate_gp3_limits(0, None, None).unwrap_err(), VolumeLimitError::SizeOutOfRange { min: 1, max: 16384, got: 0 });
+        assert_eq!(calculate_gp3_limits(100, Some(2000), None).unwrap_err(), VolumeLimitError::IopsBelowBaseline { baseline: 3000, got: 2000 });
+        assert_eq!(calculate_gp3_limits(1, Some(3000), None).unwrap_err(), VolumeLimitError::IopsToSizeRatioExceeded { max_ratio: 500, iops: 3000, size: 1 });
+        assert_eq!(calculate_gp3_limits(100, None, Some(1001)).unwrap_err(), VolumeLimitError::ThroughputOutOfRange { min: 125, max: 1000, got: 1001 });
+        assert_eq!(calculate_gp3_limits(100, None, Some(1000)).unwrap_err(), VolumeLimitError::ThroughputToIopsRatioExceeded { throughput: 1000, iops: 3000, min_iops: 4000 });
+    }
+
+    #[test]
+    fn test_error_display() {
+        let error = VolumeLimitError::SizeOutOfRange { min: 1, max: 16384, got: 0 };
+        assert_eq!(error.to_string(), "Volume size can not be less than 1GiB or greater than 16384GiB, got 0GiB.");
+    }
+
+    #[test]
+    fn test_limit_eq() {
+        let limit = calculate_gp2_limits(1500).unwrap();
+        assert_eq!(limit, Limit { iops: 4500, speed: 250, burst_iops: 0, burst_speed: 0 });
+        assert_eq!(limit.clone(), limit);
+        assert_ne!(limit, calculate_gp2_limits(20).unwrap());
+    }
+
+    #[test]
+    fn test_limit_default() {
+        assert_eq!(Limit::default(), Limit { iops: 0, speed: 0, burst_iops: 0, burst_speed: 0 });
+        assert_eq!(calculate_io1_limits(1000, 20).ok().unwrap_or_default(), Limit::default());
+    }
+
+    #[test]
+    fn test_gp3_max_iops() {
+        let limit = calculate_gp3_limits(1000, Some(16000), None).unwrap();
+        assert_eq!(limit.iops, 16000);
+        assert_eq!(calculate_gp3_limits(1000, Some(16001), None).unwrap_err(), VolumeLimitError::IopsOutOfRange { min: 3000, max: 16000, got: 16001 });
+        assert!(calculate_gp3_limits(1000, Some(30000), None).is_err());
+    }
+
+    #[test]
+    fn test_gp3_iops_to_size_ratio() {
+        assert!(calculate_gp3_limits(6, Some(3000), None).is_ok());
+        assert!(calculate_gp3_limits(6, Some(3001), None).is_err());
+        assert!(calculate_gp3_limits(20, Some(10000), None).is_ok());
+        assert_eq!(calculate_gp3_limits(20, Some(10001), None).unwrap_err(), VolumeLimitError::IopsToSizeRatioExceeded { max_ratio: 500, iops: 10001, size: 20 });
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_limit_serde_round_trip() {
+        let limit = calculate_gp2_limits(20).unwrap();
+        let json = serde_json::to_string(&limit).unwrap();
+        assert_eq!(json, r#"{"iops":100,"speed":25,"burst_iops":3000,"burst_speed":128}"#);
+        assert_eq!(serde_json::from_str::<Limit>(&json).unwrap(), limit);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_volume_type_serde_round_trip() {
+        let json = serde_json::to_string(&VolumeType::Gp3).unwrap();
+        assert_eq!(json, r#""gp3""#);
+        assert_eq!(serde_json::from_str::<VolumeType>(&json).unwrap(), VolumeType::Gp3);
+        for volume_type in VolumeType::ALL {
+            let json = serde_json::to_string(&volume_type).unwrap();
+            assert_eq!(json, format!(r#""{}""#, volume_type));
+            assert_eq!(serde_json::from_str::<VolumeType>(&json).unwrap(), volume_type);
+        }
+        assert!(serde_json::from_str::<VolumeType>(r#""Gp3""#).is_err());
+    }
+
+    #[test]
+    fn test_io2_block_express_10000() {
+        let limit = calculate_io2_block_express_limits(10000, None).unwrap();
+        assert_eq!(limit.iops, 10000);
+        assert_eq!(limit.speed, 2560);
+        assert_eq!(limit.burst_iops, 0);
+        assert_eq!(limit.burst_speed, 0);
+    }
+
+    #[test]
+    fn test_io2_block_express_100000() {
+        let limit = calculate_io2_block_express_limits(100000, Some(1000)).unwrap();
+        assert_eq!(limit.iops, 100000);
+        assert_eq!(limit.speed, 4000);
+    }
+
+    #[test]
+    fn test_io2_block_express_256000() {
+        let limit = calculate_io2_block_express_limits(256000, None).unwrap();
+        assert_eq!(limit.iops, 256000);
+        assert_eq!(limit.speed, 4000);
+        assert_eq!(calculate_io2_block_express_limits(256001, None).unwrap_err(), VolumeLimitError::IopsOutOfRange { min: 100, max: 256000, got: 256001 });
+    }
+
+    #[test]
+    fn test_io2_block_express_ratio() {
+        assert!(calculate_io2_block_express_limits(100000, Some(100)).is_ok());
+        assert_eq!(calculate_io2_block_express_limits(100001, Some(100)).unwrap_err(), VolumeLimitError::IopsToSizeRatioExceeded { max_ratio: 1000, iops: 100001, size: 100 });
+    }
+
+    #[test]
+    fn test_io1_io2_ratio() {
+        let limit = calculate_io2_limits(10, 1000).unwrap();
+        assert_eq!(limit.iops, 1000);
+        assert_eq!(limit.speed, 250);
+        assert_eq!(calculate_io1_limits(10, 1000).unwrap_err(), VolumeLimitError::IopsToSizeRatioExceeded { max_ratio: 50, iops: 1000, size: 10 });
+        assert!(calculate_io1_limits(20, 1000).is_ok());
+        assert_eq!(calculate_io2_limits(4, 2001).unwrap_err(), VolumeLimitError::IopsToSizeRatioExceeded { max_ratio: 500, iops: 2001, size: 4 });
+    }
+
+    #[test]
+    fn test_io1_io2_iops_range() {
+        assert_eq!(calculate_io1_limits(1000, 64001).unwrap_err(), VolumeLimitError::IopsOutOfRange { min: 100, max: 64000, got: 64001 });
+        assert_eq!(calculate_io2_limits(1000, 99).unwrap_err(), VolumeLimitError::IopsOutOfRange { min: 100, max: 64000, got: 99 });
+    }
+
+    #[test]
+    fn test_limit_display() {
+        assert_eq!(calculate_gp2_limits(20).unwrap().to_string(), "100 IOPS, 25 MiB/s throughput (burst: 3000 IOPS, 128 MiB/s)");
+        assert_eq!(calculate_gp2_limits(1500).unwrap().to_string(), "4500 IOPS, 250 MiB/s throughput");
+        assert_eq!(calculate_st1_limits(500).unwrap().to_string(), "0 IOPS, 19 MiB/s throughput (burst: 0 IOPS, 122 MiB/s)");
+    }
+
+    #[test]
+    fn test_min_gp2_size_for_iops_50() {
+        assert_eq!(min_gp2_size_for_iops(50).unwrap(), 1);
+        assert_eq!(min_gp2_size_for_iops(100).unwrap(), 1);
+        assert_eq!(min_gp2_size_for_iops(101).unwrap(), 34);
+    }
+
+    #[test]
+    fn test_min_gp2_size_for_iops_3000() {
+        let size = min_gp2_size_for_iops(3000).unwrap();
+        assert_eq!(size, 1000);
+        assert_eq!(calculate_gp2_limits(size).unwrap().iops, 3000);
+        assert!(calculate_gp2_limits(size - 1).unwrap().iops < 3000);
+    }
+
+    #[test]
+    fn test_min_gp2_size_for_iops_16000() {
+        let size = min_gp2_size_for_iops(16000).unwrap();
+        assert_eq!(size, 5334);
+        assert_eq!(calculate_gp2_limits(size).unwrap().iops, 16000);
+        assert!(calculate_gp2_limits(size - 1).unwrap().iops < 16000);
+    }
+
+    #[test]
+    fn test_min_gp2_size_for_iops_20000() {
+        let error = min_gp2_size_for_iops(20000).unwrap_err();
+        assert_eq!(error, VolumeLimitError::IopsTargetUnreachable { max: 16000, target: 20000 });
+        assert!(error.to_string().contains("io1 or io2"));
+    }
+
+    #[test]
+    fn test_min_gp2_size_for_throughput_100() {
+        let size = min_gp2_size_for_throughput(100).unwrap();
+        assert_eq!(size, 134);
+        assert_eq!(calculate_gp2_limits(size).unwrap().speed, 100);
+        assert!(calculate_gp2_limits(size - 1).unwrap().speed < 100);
+        assert_eq!(min_gp2_size_for_throughput(25).unwrap(), 1);
+    }
+
+    #[test]
+    fn test_min_gp2_size_for_throughput_128() {
+        let size = min_gp2_size_for_throughput(128).unwrap();
+        assert_eq!(size, 171);
+        assert_eq!(calculate_gp2_limits(size).unwrap().speed, 128);
+        assert!(calculate_gp2_limits(size - 1).unwrap().speed < 128);
+    }
+
+    #[test]
+    fn test_min_gp2_size_for_throughput_250() {
+        let size = min_gp2_size_for_throughput(250).unwrap();
+        assert_eq!(size, 334);
+        assert_eq!(calculate_gp2_limits(size).unwrap().speed, 250);
+        assert!(calculate_gp2_limits(size - 1).unwrap().speed < 250);
+    }
+
+    #[test]
+    fn test_min_gp2_size_for_throughput_300() {
+        assert_eq!(min_gp2_size_for_throughput(300).unwrap_err(), VolumeLimitError::ThroughputTargetUnreachable { max: 250, target: 300 });
+    }
+
+    #[test]
+    fn test_recommend_gp3_over_gp2() {
+        assert_eq!(recommend_volume_type(3000, 500, 100).unwrap(), VolumeType::Gp3);
+        assert_eq!(recommend_volume_type(3000, 250, 1000).unwrap(), VolumeType::Gp2);
+    }
+
+    #[test]
+    fn test_recommend_hdd() {
+        assert_eq!(recommend_volume_type(0, 10, 1000).unwrap(), VolumeType::Sc1);
+        assert_eq!(recommend_volume_type(0, 100, 4096).unwrap(), VolumeType::St1);
+    }
+
+    #[test]
+    fn test_recommend_io() {
+        assert_eq!(recommend_volume_type(30000, 500, 1000).unwrap(), VolumeType::Io1);
+        assert_eq!(recommend_volume_type(30000, 500, 100).unwrap(), VolumeType::Io2);
+    }
+
+    #[test]
+    fn test_recommend_nothing_fits() {
+        assert_eq!(recommend_volume_type(100000, 500, 1000).unwrap_err(), VolumeLimitError::NoEligibleVolumeType);
+    }
+
+    #[test]
+    fn test_gp3_throughput_to_iops_ratio() {
+        assert_eq!(calculate_gp3_limits(100, Some(4000), Some(1000)).unwrap().speed, 1000);
+        assert_eq!(calculate_gp3_limits(100, Some(3999), Some(1000)).unwrap_err(), VolumeLimitError::ThroughputToIopsRatioExceeded { throughput: 1000, iops: 3999, min_iops: 4000 });
+        assert_eq!(calculate_gp3_limits(100, Some(3200), Some(800)).unwrap().speed, 800);
+        assert_eq!(calculate_gp3_limits(100, Some(3200), Some(801)).unwrap_err(), VolumeLimitError::ThroughputToIopsRatioExceeded { throughput: 801, iops: 3200, min_iops: 3204 });
+    }
+
+    #[test]
+    fn test_gp3_throughput_boundary_with_default_iops() {
+        // 3000 baseline IOPS allow exactly 3000 / 4 = 750MiB/s
+        let limit = calculate_gp3_limits(100, None, Some(750)).unwrap();
+        assert_eq!(limit.iops, 3000);
+        assert_eq!(limit.speed, 750);
+        assert_eq!(calculate_gp3_limits(100, Some(3000), Some(750)).unwrap(), limit);
+        assert_eq!(calculate_gp3_limits(100, None, Some(751)).unwrap_err(), VolumeLimitError::ThroughputToIopsRatioExceeded { throughput: 751, iops: 3000, min_iops: 3004 });
+        assert_eq!(calculate_gp3_limits(100, Some(3000), Some(751)).unwrap_err(), VolumeLimitError::ThroughputToIopsRatioExceeded { throughput: 751, iops: 3000, min_iops: 3004 });
+    }
+
+    #[test]
+    fn test_size_unit_to_gib() {
+        assert_eq!(SizeUnit::TiB.to_gib(1.5), 1536);
+        assert_eq!(SizeUnit::TiB.to_gib(0.0005), 1);
+        assert_eq!(SizeUnit::GiB.to_gib(499.4), 499);
+        assert_eq!(SizeUnit::GiB.to_gib(-5.0), 0);
+    }
+
+    #[test]
+    fn test_calculate_with_unit_tib() {
+        let limit = calculate_with_unit(VolumeType::Gp3, 1.5, SizeUnit::TiB, Some(16000), Some(1000)).unwrap();
+        assert_eq!(limit, calculate_gp3_limits(1536, Some(16000), Some(1000)).unwrap());
+        let limit = calculate_with_unit(VolumeType::Gp2, 1.5, SizeUnit::TiB, None, None).unwrap();
+        assert_eq!(limit, calculate_gp2_limits(1536).unwrap());
+    }
+
+    #[test]
+    fn test_calculate_with_unit_out_of_range() {
+        assert_eq!(calculate_with_unit(VolumeType::Gp2, 16.5, SizeUnit::TiB, None, None).unwrap_err(), VolumeLimitError::SizeOutOfRange { min: 1, max: 16384, got: 16896 });
+        assert!(calculate_with_unit(VolumeType::St1, 0.1, SizeUnit::TiB, None, None).is_err());
+    }
+
+    #[test]
+    fn test_gp2_burst_duration_100() {
+        assert_eq!(gp2_burst_duration_seconds(100).unwrap(), 2000);
+    }
+
+    #[test]
+    fn test_gp2_burst_duration_500() {
+        assert_eq!(gp2_burst_duration_seconds(500).unwrap(), 3600);
+    }
+
+    #[test]
+    fn test_gp2_burst_duration_no_burst() {
+        assert_eq!(gp2_burst_duration_seconds(1000).unwrap(), 0);
+        assert_eq!(gp2_burst_duration_seconds(3000).unwrap(), 0);
+        assert!(gp2_burst_duration_seconds(0).is_err());
+    }
+
+    #[test]
+    fn test_standard_1() {
+        let limit = calculate_standard_limits(1).unwrap();
+        assert_eq!(limit.iops, 100);
+        assert_eq!(limit.speed, 40);
+        assert_eq!(limit.burst_iops, 200);
+        assert_eq!(limit.burst_speed, 90);
+    }
+
+    #[test]
+    fn test_standard_500() {
+        let limit = calculate_standard_limits(500).unwrap();
+        assert_eq!(limit, calculate_standard_limits(1).unwrap());
+        assert_eq!(calculate(VolumeType::Standard, 500, None, None).unwrap(), limit);
+    }
+
+    #[test]
+    fn test_standard_1025() {
+        assert_eq!(calculate_standard_limits(1025).unwrap_err(), VolumeLimitError::SizeOutOfRange { min: 1, max: 1024, got: 1025 });
+    }
+
+    #[test]
+    fn test_gp2_detailed_1500() {
+        let detailed = calculate_gp2_limits_detailed(1500).unwrap();
+        assert_eq!(detailed.limit, calculate_gp2_limits(1500).unwrap());
+        assert_eq!(detailed.limit.speed, 250);
+        assert_eq!(detailed.binding_constraint, BindingConstraint::ThroughputCeiling);
+    }
+
+    #[test]
+    fn test_gp2_detailed_10000() {
+        let detailed = calculate_gp2_limits_detailed(10000).unwrap();
+        assert_eq!(detailed.limit.iops, 16000);
+        assert_eq!(detailed.binding_constraint, BindingConstraint::IopsCeiling);
+    }
+
+    #[test]
+    fn test_gp2_detailed_small() {
+        assert_eq!(calculate_gp2_limits_detailed(20).unwrap().binding_constraint, BindingConstraint::IopsToThroughputConversion);
+        assert_eq!(calculate_gp2_limits_detailed(333).unwrap().binding_constraint, BindingConstraint::IopsToThroughputConversion);
+        assert_eq!(calculate_gp2_limits_detailed(334).unwrap().binding_constraint, BindingConstraint::ThroughputCeiling);
+        assert!(calculate_gp2_limits_detailed(0).is_err());
+    }
+
+    #[test]
+    fn test_limit_to_json() {
+        assert_eq!(calculate_gp2_limits(1500).unwrap().to_json(), r#"{"iops":4500,"speed":250,"burst_iops":0,"burst_speed":0}"#);
+        assert_eq!(calculate_gp2_limits(20).unwrap().to_json(), r#"{"iops":100,"speed":25,"burst_iops":3000,"burst_speed":128}"#);
+    }
+
+    #[test]
+    fn test_gp3_ratio_error_suggests_iops() {
+        let error = calculate_gp3_limits(1000, None, Some(800)).unwrap_err();
+        assert_eq!(error, VolumeLimitError::ThroughputToIopsRatioExceeded { throughput: 800, iops: 3000, min_iops: 3200 });
+        assert!(error.to_string().contains("at least 3200 IOPS"));
+    }
+
+    #[test]
+    fn test_volume_type_from_str() {
+        assert_eq!("gp2".parse::<VolumeType>().unwrap(), VolumeType::Gp2);
+        assert_eq!("gp3".parse::<VolumeType>().unwrap(), VolumeType::Gp3);
+        assert_eq!("io1".parse::<VolumeType>().unwrap(), VolumeType::Io1);
+        assert_eq!("io2".parse::<VolumeType>().unwrap(), VolumeType::Io2);
+        assert_eq!("st1".parse::<VolumeType>().unwrap(), VolumeType::St1);
+        assert_eq!("sc1".parse::<VolumeType>().unwrap(), VolumeType::Sc1);
+        assert_eq!("standard".parse::<VolumeType>().unwrap(), VolumeType::Standard);
+    }
+
+    #[test]
+    fn test_volume_type_from_str_uppercase() {
+        assert_eq!("GP3".parse::<VolumeType>().unwrap(), VolumeType::Gp3);
+        assert_eq!("Standard".parse::<VolumeType>().unwrap(), VolumeType::Standard);
+    }
+
+    #[test]
+    fn test_volume_type_from_str_garbage() {
+        assert_eq!("gp4".parse::<VolumeType>().unwrap_err(), VolumeLimitError::UnknownVolumeType);
+        assert_eq!("".parse::<VolumeType>().unwrap_err(), VolumeLimitError::UnknownVolumeType);
+        assert_eq!(" gp3".parse::<VolumeType>().unwrap_err(), VolumeLimitError::UnknownVolumeType);
+    }
+
+    #[test]
+    fn test_volume_type_display() {
+        assert_eq!(VolumeType::Gp3.to_string(), "gp3");
+        assert_eq!(VolumeType::Standard.to_string(), "standard");
+    }
+
+    #[test]
+    fn test_volume_type_display_round_trip() {
+        for volume_type in VolumeType::ALL {
+            assert_eq!(volume_type.to_string().parse::<VolumeType>().unwrap(), volume_type);
+        }
+    }
+
+    const GP2_1000: Limit = match calculate_gp2_limits(1000) {
+        Ok(limit) => limit,
+        Err(_) => panic!("1000GiB is a valid gp2 volume size"),
+    };
+
+    #[allow(deprecated)]
+    const IO_10000: Limit = match calculate_io_limits(10000) {
+        Ok(limit) => limit,
+        Err(_) => panic!("10000 IOPS is valid for io1/io2"),
+    };
+
+    const IO2_SIZE_ERROR: Result<Limit, VolumeLimitError> = calculate_io2_limits(4, 2001);
+
+    #[test]
+    fn test_const_limits() {
+        assert_eq!(GP2_1000, calculate_gp2_limits(1000).unwrap());
+        assert_eq!(IO_10000.speed, 500);
+        assert_eq!(IO2_SIZE_ERROR, Err(VolumeLimitError::IopsToSizeRatioExceeded { max_ratio: 500, iops: 2001, size: 4 }));
+    }
+
+    #[test]
+    fn test_speed_never_exceeds_type_maximum() {
+        for size in 1..=16384 {
+            let gp2_max = if size <= 170 { 128 } else { 250 };
+            let limit = calculate_gp2_limits(size).unwrap();
+            assert!(limit.speed <= gp2_max && limit.burst_speed <= gp2_max, "gp2 {}GiB: {:?}", size, limit);
+
+            let limit = calculate_gp3_limits(size, None, None).unwrap();
+            assert!(limit.speed <= 1000, "gp3 {}GiB: {:?}", size, limit);
+            let limit = max_capability(VolumeType::Gp3, size).unwrap();
+            assert!(limit.speed <= 1000, "gp3 {}GiB provisioned to the maximum: {:?}", size, limit);
+
+            for volume_type in [VolumeType::Io1, VolumeType::Io2] {
+                if let Ok(limit) = max_capability(volume_type, size) {
+                    assert!(limit.speed <= 1000, "{:?} {}GiB: {:?}", volume_type, size, limit);
+                }
+            }
+
+            if let Ok(limit) = calculate_st1_limits(size) {
+                assert!(limit.speed <= 500 && limit.burst_speed <= 500, "st1 {}GiB: {:?}", size, limit);
+            }
+            if let Ok(limit) = calculate_sc1_limits(size) {
+                assert!(limit.speed <= 192 && limit.burst_speed <= 250, "sc1 {}GiB: {:?}", size, limit);
+            }
+            if let Ok(limit) = calculate_standard_limits(size) {
+                assert!(limit.speed <= 90 && limit.burst_speed <= 90, "standard {}GiB: {:?}", size, limit);
+            }
+        }
+
+        for iops in 100..=64000 {
+            let io_max = if iops < 32000 { 500 } else { 1000 };
+            let limit = calculate_io2_limits(16384, iops).unwrap();
+            assert!(limit.speed <= io_max, "io {} IOPS: {:?}", iops, limit);
+        }
+
+        for iops in (100..=256000).step_by(7) {
+            let limit = calculate_io2_block_express_limits(iops, None).unwrap();
+            assert!(limit.speed <= 4000, "io2 Block Express {} IOPS: {:?}", iops, limit);
+        }
+    }
+
+    #[test]
+    fn test_gp3_builder() {
+        let limit = Gp3Builder::new().size(500).throughput(250).build().unwrap();
+        assert_eq!(limit, calculate_gp3_limits(500, None, Some(250)).unwrap());
+        assert_eq!(limit.iops, 3000);
+        assert_eq!(limit.speed, 250);
+
+        assert!(matches!(Gp3Builder::new().build(), Err(VolumeLimitError::SizeOutOfRange { .. })));
+    }
+
+    #[test]
+    fn test_gp3_baseline_constants() {
+        let limit = calculate_gp3_limits(100, None, None).unwrap();
+        assert_eq!(limit, Limit { iops: GP3_BASELINE_IOPS, speed: GP3_BASELINE_THROUGHPUT, burst_iops: 0, burst_speed: 0 });
+        assert_eq!(calculate_gp2_limits(100).unwrap().burst_iops, GP2_BURST_IOPS);
+    }
+
+    #[test]
+    fn test_limit_changes_on_resize() {
+        assert!(limit_changes_on_resize(VolumeType::Gp2, 100, 200).unwrap());
+        assert!(limit_changes_on_resize(VolumeType::St1, 500, 1000).unwrap());
+    }
+
+    #[test]
+    fn test_limit_changes_on_resize_past_cap() {
+        assert!(!limit_changes_on_resize(VolumeType::Gp2, 6000, 8000).unwrap());
+        assert!(!limit_changes_on_resize(VolumeType::Gp3, 100, 1000).unwrap());
+        assert!(limit_changes_on_resize(VolumeType::Gp2, 6000, 20000).is_err());
+    }
+
+    #[test]
+    fn test_limit_diff() {
+        let small = calculate_gp2_limits(500).unwrap();
+        let large = calculate_gp2_limits(1000).unwrap();
+        let diff = small.diff(&large);
+        assert_eq!(diff, LimitDiff { iops: 1500, speed: 0, burst_iops: 0, burst_speed: 0 });
+        assert_eq!(diff.to_string(), "+1500 IOPS, +0 MiB/s");
+        assert_eq!(large.diff(&small).to_string(), "-1500 IOPS, +0 MiB/s");
+        assert_eq!(large.diff(&calculate_gp2_limits(2000).unwrap()).to_string(), "+3000 IOPS, +0 MiB/s (burst: -3000 IOPS, -250 MiB/s)");
+    }
+
+    #[test]
+    fn test_gp3_max_limit() {
+        assert_eq!(gp3_max_limit(100).unwrap(), Limit { iops: 16000, speed: 1000, burst_iops: 0, burst_speed: 0 });
+        assert_eq!(gp3_max_limit(8).unwrap(), Limit { iops: 4000, speed: 1000, burst_iops: 0, burst_speed: 0 });
+        assert_eq!(gp3_max_limit(1).unwrap(), Limit { iops: 3000, speed: 750, burst_iops: 0, burst_speed: 0 });
+        assert!(gp3_max_limit(0).is_err());
+    }
+
+    #[test]
+    fn test_suggest_downgrade_to_gp2() {
+        let suggestion = suggest_downgrade_to_gp2(1000, 3000, 125).unwrap();
+        assert_eq!(suggestion, "gp2 at 1000GiB provides 3000 IOPS and 250 MiB/s, which meets the requested 3000 IOPS and 125 MiB/s without provisioning gp3.");
+        assert!(suggest_downgrade_to_gp2(1000, 3000, 250).is_some());
+        assert_eq!(suggest_downgrade_to_gp2(100, 3000, 125), None);
+        assert_eq!(suggest_downgrade_to_gp2(1000, 4000, 125), None);
+        assert_eq!(suggest_downgrade_to_gp2(0, 3000, 125), None);
+    }
+
+    #[test]
+    fn test_gp2_170_boundary() {
+        assert_eq!(calculate_gp2_limits(169).unwrap(), Limit { iops: 507, speed: 126, burst_iops: 3000, burst_speed: 128 });
+        assert_eq!(calculate_gp2_limits(170).unwrap(), Limit { iops: 510, speed: 127, burst_iops: 3000, burst_speed: 128 });
+        assert_eq!(calculate_gp2_limits(171).unwrap(), Limit { iops: 513, speed: 128, burst_iops: 3000, burst_speed: 250 });
+    }
+
+    #[test]
+    fn test_speed_mb() {
+        let limit = calculate_gp2_limits(1000).unwrap();
+        assert!((limit.speed_mb() - 262.144).abs() < 1e-9);
+        assert!((limit.burst_speed_mb() - 262.144).abs() < 1e-9);
+        assert_eq!(calculate_gp2_limits(2000).unwrap().burst_speed_mb(), 0.0);
+    }
+
+    #[test]
+    fn test_gp2_detailed_ceiling_small() {
+        let detailed = calculate_gp2_limits_detailed(20).unwrap();
+        assert_eq!(detailed.limit.speed, 25);
+        assert_eq!(detailed.max_speed, 128);
+        assert_eq!(detailed.max_iops, 3000);
+    }
+
+    #[test]
+    fn test_gp2_detailed_ceiling_large() {
+        let detailed = calculate_gp2_limits_detailed(2000).unwrap();
+        assert_eq!(detailed.max_speed, 250);
+        assert_eq!(detailed.max_iops, 6000);
+    }
+
+    #[test]
+    fn test_io_limits_sized_io1_ratio() {
+        assert_eq!(calculate_io_limits_sized(10, 1000, false).unwrap_err(), VolumeLimitError::IopsToSizeRatioExceeded { max_ratio: 50, iops: 1000, size: 10 });
+        assert_eq!(calculate_io_limits_sized(10, 500, false).unwrap(), calculate_io1_limits(10, 500).unwrap());
+        assert!(calculate_io_limits_sized(10, 501, false).is_err());
+    }
+
+    #[test]
+    fn test_io_limits_sized_io2_ratio() {
+        assert_eq!(calculate_io_limits_sized(10, 1000, true).unwrap(), calculate_io2_limits(10, 1000).unwrap());
+        assert!(calculate_io_limits_sized(10, 5000, true).is_ok());
+        assert_eq!(calculate_io_limits_sized(10, 5001, true).unwrap_err(), VolumeLimitError::IopsToSizeRatioExceeded { max_ratio: 500, iops: 5001, size: 10 });
+    }
+
+    #[test]
+    fn test_io_ratio_with_huge_size() {
+        assert!(calculate_io1_limits(u32::MAX, 64000).is_ok());
+        assert_eq!(calculate_io2_limits(u32::MAX, 64000).unwrap_err(), VolumeLimitError::SizeOutOfRange { min: 4, max: 16384, got: u32::MAX });
+        assert!(calculate_io2_block_express_limits(256000, Some(u32::MAX)).is_ok());
+        assert!(limit_changes_on_resize(VolumeType::Io1, u32::MAX - 1, u32::MAX).is_ok());
+    }
+
+    #[test]
+    fn test_restore_performance_note() {
+        assert!(restore_performance_note(VolumeType::Gp2).unwrap().contains("fast snapshot restore"));
+        assert!(restore_performance_note(VolumeType::Sc1).unwrap().contains("sequential throughput"));
+        assert!(VolumeType::ALL.iter().all(|volume_type| restore_performance_note(*volume_type).is_some()));
+    }
+
+    #[test]
+    fn test_gp2_reaches_iops_cap() {
+        assert!(!gp2_reaches_iops_cap(5333));
+        assert!(gp2_reaches_iops_cap(5334));
+        assert!(gp2_reaches_iops_cap(u32::MAX));
+        assert_eq!(calculate_gp2_limits(5333).unwrap().iops, 15999);
+        assert_eq!(calculate_gp2_limits(5334).unwrap().iops, 16000);
+    }
+
+    #[test]
+    fn test_eligible_volume_types_throughput_heavy() {
+        let eligible = eligible_volume_types(0, 200, 16384);
+        assert_eq!(eligible, [VolumeType::St1, VolumeType::Gp2, VolumeType::Gp3, VolumeType::Io1, VolumeType::Io2]);
+        assert!(!eligible.contains(&VolumeType::Sc1));
+        assert!(eligible_volume_types(100000, 0, 16384).is_empty());
+    }
+
+    #[test]
+    fn test_gp3_max_throughput_for_iops() {
+        assert_eq!(gp3_max_throughput_for_iops(2000), 500);
+        assert_eq!(gp3_max_throughput_for_iops(4000), 1000);
+        assert_eq!(gp3_max_throughput_for_iops(8000), 1000);
+        assert!(calculate_gp3_limits(1000, Some(3000), Some(gp3_max_throughput_for_iops(3000))).is_ok());
+    }
+
+    #[test]
+    fn test_gp3_iops_below_baseline() {
+        let error = calculate_gp3_limits(100, Some(2999), None).unwrap_err();
+        assert_eq!(error, VolumeLimitError::IopsBelowBaseline { baseline: 3000, got: 2999 });
+        assert!(error.to_string().contains("Leave IOPS unset"));
+        assert_eq!(calculate_gp3_limits(100, None, None).unwrap().iops, 3000);
+    }
+
+    #[test]
+    fn test_is_valid_gp3() {
+        assert!(is_valid_gp3(100, None, None));
+        assert!(is_valid_gp3(100, Some(6000), Some(500)));
+        assert!(!is_valid_gp3(0, None, None));                       // size out of range
+        assert!(!is_valid_gp3(100, Some(2999), None));               // below the IOPS baseline
+        assert!(!is_valid_gp3(100, Some(16001), None));              // above the IOPS maximum
+        assert!(!is_valid_gp3(5, Some(3000), None));                 // IOPS to size ratio
+        assert!(!is_valid_gp3(100, None, Some(124)));                // below the throughput baseline
+        assert!(!is_valid_gp3(100, Some(16000), Some(1001)));        // above the throughput maximum
+        assert!(!is_valid_gp3(100, None, Some(751)));                // throughput to IOPS ratio
+    }
+
+    #[test]
+    fn test_is_valid_other_types() {
+        assert!(is_valid_gp2(1) && is_valid_gp2(16384));
+        assert!(!is_valid_gp2(0) && !is_valid_gp2(16385));
+        assert!(is_valid_io1(100, 5000) && !is_valid_io1(100, 5001) && !is_valid_io1(100, 99));
+        assert!(is_valid_io2(100, 50000) && !is_valid_io2(100, 50001) && !is_valid_io2(1000, 64001));
+        assert!(is_valid_st1(125) && !is_valid_st1(124) && !is_valid_st1(16385));
+        assert!(is_valid_sc1(125) && !is_valid_sc1(124) && !is_valid_sc1(16385));
+        assert!(is_valid_standard(1) && !is_valid_standard(0) && !is_valid_standard(1025));
+    }
+
+    #[test]
+    fn test_from_create_volume_request_gp3() {
+        let limit = from_create_volume_request("gp3", 500, Some(6000), Some(250)).unwrap();
+        assert_eq!(limit, calculate_gp3_limits(500, Some(6000), Some(250)).unwrap());
+        assert_eq!(from_create_volume_request("gp3", 500, None, None).unwrap(), calculate_gp3_limits(500, None, None).unwrap());
+    }
+
+    #[test]
+    fn test_from_create_volume_request_invalid() {
+        assert_eq!(from_create_volume_request("gp3", -1, None, None).unwrap_err(), VolumeLimitError::NegativeParameter { name: "Size", got: -1 });
+        assert_eq!(from_create_volume_request("io2", 100, Some(-100), None).unwrap_err(), VolumeLimitError::NegativeParameter { name: "Iops", got: -100 });
+        assert_eq!(from_create_volume_request("gp3", 100, None, Some(-5)).unwrap_err().to_string(), "Throughput can not be negative, got -5.");
+        assert_eq!(from_create_volume_request("gp4", 100, None, None).unwrap_err(), VolumeLimitError::UnknownVolumeType);
+    }
+
+    #[test]
+    fn test_explain_gp2_floor() {
+        let text = explain(VolumeType::Gp2, 20, None, None).unwrap();
+        assert_eq!(text, "A 20 GiB gp2 volume gets 100 baseline IOPS (the 100 IOPS minimum), 25 MiB/s throughput (converted from IOPS at 256KiB per I/O), and can burst to 3000 IOPS and 128 MiB/s.");
+    }
+
+    #[test]
+    fn test_explain_rules() {
+        assert!(explain(VolumeType::Gp2, 6000, None, None).unwrap().contains("capped at the 16000 IOPS maximum"));
+        assert!(explain(VolumeType::Gp2, 500, None, None).unwrap().contains("3 IOPS per GiB"));
+        assert!(explain(VolumeType::Gp3, 100, None, None).unwrap().contains("3000 IOPS (the included baseline)"));
+        assert!(explain(VolumeType::Io1, 1000, Some(10000), None).unwrap().contains("capped at the maximum"));
+        assert!(explain(VolumeType::Io1, 1000, Some(1000), None).unwrap().contains("converted from the provisioned IOPS"));
+        assert!(explain(VolumeType::Gp2, 0, None, None).is_err());
+    }
+
+    #[test]
+    fn test_gp2_clamped() {
+        assert_eq!(calculate_gp2_limits_clamped(0), calculate_gp2_limits(1).unwrap());
+        assert_eq!(calculate_gp2_limits_clamped(20000), calculate_gp2_limits(16384).unwrap());
+        assert_eq!(calculate_gp2_limits_clamped(500), calculate_gp2_limits(500).unwrap());
+    }
+
+    #[test]
+    fn test_into_performance_gp2() {
+        let performance = calculate_gp2_limits(100).unwrap().into_performance();
+        assert_eq!(performance.baseline, IopsThroughput { iops: 300, speed: 75 });
+        assert_eq!(performance.burst, Some(IopsThroughput { iops: 3000, speed: 128 }));
+    }
+
+    #[test]
+    fn test_into_performance_gp3() {
+        let performance = calculate_gp3_limits(100, None, None).unwrap().into_performance();
+        assert_eq!(performance.baseline, IopsThroughput { iops: 3000, speed: 125 });
+        assert_eq!(performance.burst, None);
+    }
+
+    #[test]
+    fn test_gp2_burst_advantage() {
+        assert_eq!(gp2_burst_advantage(100).unwrap(), (2700, 53));
+        assert_eq!(gp2_burst_advantage(1000).unwrap(), (0, 0));
+        assert_eq!(gp2_burst_advantage(2000).unwrap(), (0, 0));
+    }
+
+    #[test]
+    fn test_gp2_iops_curve() {
+        let start: Vec<_> = gp2_iops_curve(100).take(3).collect();
+        assert_eq!(start, [(1, 100), (101, 303), (201, 603)]);
+        let end: Vec<_> = gp2_iops_curve(1000).skip(5).collect();
+        assert_eq!(end.first(), Some(&(5001, 15003)));
+        assert!(end.iter().skip(1).all(|&(_, iops)| iops == 16000));
+        assert_eq!(end.last(), Some(&(16001, 16000)));
+        assert_eq!(gp2_iops_curve(1).count(), 16384);
+        assert_eq!(gp2_iops_curve(0).count(), 0);
+    }
+
+    #[test]
+    fn test_gp3_builder_auto_scale_iops() {
+        let limit = Gp3Builder::new().size(100).throughput(800).auto_scale_iops(true).build().unwrap();
+        assert_eq!(limit, Limit { iops: 3200, speed: 800, burst_iops: 0, burst_speed: 0 });
+        assert_eq!(Gp3Builder::new().size(100).throughput(500).auto_scale_iops(true).build().unwrap().iops, 3000);
+        assert!(matches!(Gp3Builder::new().size(100).throughput(800).build(), Err(VolumeLimitError::ThroughputToIopsRatioExceeded { .. })));
+        assert!(matches!(Gp3Builder::new().size(100).iops(3000).throughput(800).auto_scale_iops(true).build(), Err(VolumeLimitError::ThroughputToIopsRatioExceeded { .. })));
+    }
+
+    #[test]
+    fn test_io_limits_rounding_modes() {
+        let speed = |iops, rounding| calculate_io_limits_with_rounding(1000, iops, false, rounding).unwrap().speed;
+        assert_eq!(speed(1500, RoundingMode::Floor), 375);
+        assert_eq!(speed(1500, RoundingMode::Nearest), 375);
+        assert_eq!(speed(1500, RoundingMode::Ceil), 375);
+        assert_eq!(speed(1501, RoundingMode::Floor), 375);
+        assert_eq!(speed(1501, RoundingMode::Nearest), 375);
+        assert_eq!(speed(1501, RoundingMode::Ceil), 376);
+        assert_eq!(speed(1502, RoundingMode::Floor), 375);
+        assert_eq!(speed(1502, RoundingMode::Nearest), 376);
+        assert_eq!(calculate_io_limits_with_rounding(1000, 32033, true, RoundingMode::Nearest).unwrap().speed, 501);
+        assert_eq!(calculate_io_limits_with_rounding(1000, 32033, true, RoundingMode::default()).unwrap(), calculate_io2_limits(1000, 32033).unwrap());
+        assert_eq!(calculate_io_limits_with_rounding(10, 1000, false, RoundingMode::Ceil).unwrap_err(), VolumeLimitError::IopsToSizeRatioExceeded { max_ratio: 50, iops: 1000, size: 10 });
+    }
+
+    #[test]
+    fn test_io_throughput_is_iops_limited() {
+        assert!(io_throughput_is_iops_limited(1000));
+        assert!(io_throughput_is_iops_limited(1999));
+        assert!(!io_throughput_is_iops_limited(2000));
+        assert!(!io_throughput_is_iops_limited(31999));
+        assert!(io_throughput_is_iops_limited(32000));
+        assert!(io_throughput_is_iops_limited(63999));
+        assert!(!io_throughput_is_iops_limited(64000));
+    }
+
+    #[test]
+    fn test_limit_meets() {
+        let limit = calculate_gp2_limits(100).unwrap();
+        assert!(limit.meets(300, 75));
+        assert!(!limit.meets(301, 75));
+        assert!(!limit.meets(300, 76));
+    }
+
+    #[test]
+    fn test_limit_meets_with_burst() {
+        let limit = calculate_gp2_limits(100).unwrap();
+        assert!(limit.meets_with_burst(3000, 128));
+        assert!(!limit.meets_with_burst(3001, 128));
+        assert!(!limit.meets_with_burst(3000, 129));
+        assert!(calculate_gp2_limits(2000).unwrap().meets_with_burst(6000, 250));
+    }
+
+    #[test]
+    fn test_gp3_strict_max_iops() {
+        assert_eq!(calculate_gp3_limits_with_bound(100, Some(20000), None, true).unwrap_err(), VolumeLimitError::IopsOutOfRange { min: 3000, max: Gp3Limits::STRICT_MAX_IOPS, got: 20000 });
+        assert_eq!(calculate_gp3_limits(100, Some(20000), None), calculate_gp3_limits_with_bound(100, Some(20000), None, true));
+    }
+
+    #[test]
+    fn test_gp3_legacy_max_iops() {
+        assert_eq!(calculate_gp3_limits_with_bound(100, Some(20000), None, false).unwrap().iops, 20000);
+        assert_eq!(calculate_gp3_limits_with_bound(1000, Some(64001), None, false).unwrap_err(), VolumeLimitError::IopsOutOfRange { min: 3000, max: Gp3Limits::LEGACY_MAX_IOPS, got: 64001 });
+        assert!(calculate_gp3_limits_with_bound(10, Some(20000), None, false).is_err());       // the 500:1 ratio still applies
+    }
+
+    #[test]
+    fn test_parse_size_to_gib() {
+        assert_eq!(parse_size_to_gib("512").unwrap(), 512);
+        assert_eq!(parse_size_to_gib("500GiB").unwrap(), 500);
+        assert_eq!(parse_size_to_gib("1TiB").unwrap(), 1024);
+        assert_eq!(parse_size_to_gib("1.5tib").unwrap(), 1536);
+        assert_eq!(parse_size_to_gib("2TB").unwrap(), 1863);                 // 2 * 10^12 bytes is 1862.6GiB
+        assert_eq!(parse_size_to_gib("100 gb").unwrap(), 94);
+        assert_eq!(parse_size_to_gib("0.5").unwrap(), 1);
+    }
+
+    #[test]
+    fn test_parse_size_to_gib_invalid() {
+        assert_eq!(parse_size_to_gib("bad").unwrap_err(), VolumeLimitError::UnparseableSize);
+        assert_eq!(parse_size_to_gib("-1GiB").unwrap_err(), VolumeLimitError::UnparseableSize);
+        assert_eq!(parse_size_to_gib("GiB").unwrap_err(), VolumeLimitError::UnparseableSize);
+        assert_eq!(parse_size_to_gib("inf").unwrap_err(), VolumeLimitError::UnparseableSize);
+        assert_eq!(parse_size_to_gib("").unwrap_err(), VolumeLimitError::UnparseableSize);
+        assert_eq!(parse_size_to_gib("5000000TiB").unwrap_err(), VolumeLimitError::Overflow);
+    }
+
+    #[test]
+    fn test_baseline_accessors_gp2() {
+        assert_eq!(baseline_iops(VolumeType::Gp2, 100).unwrap(), 300);
+        assert_eq!(baseline_throughput(VolumeType::Gp2, 100).unwrap(), 75);
+        assert_eq!(baseline_iops(VolumeType::Gp2, 20000).unwrap_err(), VolumeLimitError::SizeOutOfRange { min: 1, max: 16384, got: 20000 });
+    }
+
+    #[test]
+    fn test_baseline_accessors_st1() {
+        assert_eq!(baseline_iops(VolumeType::St1, 2048).unwrap(), 0);
+        assert_eq!(baseline_throughput(VolumeType::St1, 2048).unwrap(), 80);
+        assert_eq!(baseline_throughput(VolumeType::St1, 100).unwrap_err(), VolumeLimitError::SizeOutOfRange { min: 125, max: 16384, got: 100 });
+        assert_eq!(baseline_iops(VolumeType::Io1, 100).unwrap_err(), VolumeLimitError::MissingProvisionedIops);
+    }
+
+    #[test]
+    fn test_io1_32000_iops_boundary() {
+        assert_eq!(calculate_io1_limits(1000, 31999).unwrap().speed, 500);
+        assert_eq!(calculate_io1_limits(1000, 32000).unwrap().speed, 500);
+        assert_eq!(calculate_io1_limits(1000, 32001).unwrap().speed, 500);
+        assert_eq!(calculate_io1_limits(1000, 32064).unwrap().speed, 501);
+    }
+
+    #[test]
+    fn test_warn_small_volume_high_throughput() {
+        let warning = warn_small_volume_high_throughput(10, 1000).unwrap();
+        assert_eq!(warning, "1000MiB/s of throughput on a 10GiB volume is more than 1MiB/s per GiB, check that the volume isn't undersized.");
+        assert_eq!(warn_small_volume_high_throughput(1000, 1000), None);
+        assert!(warn_small_volume_high_throughput(100, 125).is_some());
+        assert_eq!(warn_small_volume_high_throughput(500, 250), None);
+    }
+
+    #[test]
+    fn test_calculate_typed() {
+        let limit = calculate_typed(VolumeType::Gp3, SizeGib(500), Some(Iops(6000)), Some(ThroughputMib(250))).unwrap();
+        assert_eq!(limit, calculate(VolumeType::Gp3, 500, Some(6000), Some(250)).unwrap());
+        let limit = calculate_typed(VolumeType::Io2, 100.into(), Some(10000.into()), None).unwrap();
+        assert_eq!(limit, calculate_io2_limits(100, 10000).unwrap());
+        assert_eq!(calculate_typed(VolumeType::Gp2, SizeGib(0), None, None).unwrap_err(), VolumeLimitError::SizeOutOfRange { min: 1, max: 16384, got: 0 });
+    }
+
+    #[test]
+    fn test_io2_size_range() {
+        assert!(calculate_io2_limits(4, 100).is_ok());
+        assert!(calculate_io_limits_sized(4, 2000, true).is_ok());
+        assert_eq!(calculate_io2_limits(2, 100).unwrap_err(), VolumeLimitError::SizeOutOfRange { min: 4, max: 16384, got: 2 });
+        assert_eq!(calculate_io_limits_sized(2, 100, true).unwrap_err(), VolumeLimitError::SizeOutOfRange { min: 4, max: 16384, got: 2 });
+        assert!(calculate_io2_limits(16385, 100).is_err());
+    }
+
+    #[test]
+    fn test_gp2_throughput_cap_size() {
+        let size = gp2_throughput_cap_size();
+        assert_eq!(size, 334);
+        assert_eq!(calculate_gp2_limits(size).unwrap().speed, 250);
+        assert!(calculate_gp2_limits(size - 1).unwrap().speed < 250);
+        assert_eq!(min_gp2_size_for_throughput(250).unwrap(), size);
+    }
+
+    #[test]
+    fn test_has_burst() {
+        assert!(calculate_gp2_limits(20).unwrap().has_burst());
+        assert!(calculate_st1_limits(500).unwrap().has_burst());
+        assert!(calculate_standard_limits(100).unwrap().has_burst());
+        assert!(!calculate_gp3_limits(100, None, None).unwrap().has_burst());
+        assert!(!calculate_io1_limits(100, 1000).unwrap().has_burst());
+        assert!(!calculate_gp2_limits(2000).unwrap().has_burst());
+    }
+
+    #[test]
+    fn test_gp2_credit_fill_rate() {
+        assert_eq!(gp2_credit_fill_rate(200).unwrap(), calculate_gp2_limits(200).unwrap().iops);
+        assert_eq!(gp2_credit_fill_rate(200).unwrap(), 600);
+        assert_eq!(gp2_credit_fill_rate(10).unwrap(), 100);
+        assert!(gp2_credit_fill_rate(0).is_err());
+    }
+
+    #[test]
+    fn test_effective_iops_at_block_size() {
+        assert_eq!(effective_iops_at_block_size(250, 16), 16000);
+        assert_eq!(effective_iops_at_block_size(250, 256), 1000);
+        assert_eq!(effective_iops_at_block_size(250, 0), 0);
+        assert_eq!(effective_iops_at_block_size(u32::MAX, 1), u32::MAX);
+    }
+
+    #[test]
+    fn test_io_limits_block() {
+        assert_eq!(calculate_io_limits_block(1000, 32).unwrap().speed, 31);
+        assert_eq!(calculate_io_limits_block(1000, 128).unwrap().speed, 125);
+        assert_eq!(calculate_io_limits_block(10000, 128).unwrap().speed, 500);
+        assert_eq!(calculate_io_limits_block(40000, 32).unwrap().speed, 1000);
+        assert_eq!(calculate_io_limits_block(50, 32).unwrap_err(), VolumeLimitError::IopsOutOfRange { min: 100, max: 64000, got: 50 });
+    }
+
+    #[test]
+    fn test_io_limits_block_default_sizes() {
+        assert_eq!(calculate_io_limits_block(1500, IO_MAX_BLOCK_KIB).unwrap(), calculate_io1_limits(1000, 1500).unwrap());
+        assert_eq!(calculate_io_limits_block(40000, IO_HIGH_IOPS_MAX_BLOCK_KIB).unwrap(), calculate_io1_limits(1000, 40000).unwrap());
+    }
+
+    #[test]
+    fn test_limit_into_tuple() {
+        let limit = Limit { iops: 1, speed: 2, burst_iops: 3, burst_speed: 4 };
+        assert_eq!(<(u32, u32, u32, u32)>::from(limit), (1, 2, 3, 4));
+        let (iops, speed, _, _) = calculate_gp2_limits(20).unwrap().into();
+        assert_eq!((iops, speed), (100, 25));
+    }
+
+    #[test]
+    fn test_validate_type_change() {
+        assert_eq!(validate_type_change(VolumeType::Gp2, VolumeType::Gp3, 100).unwrap(), calculate_gp3_limits(100, None, None).unwrap());
+        assert_eq!(validate_type_change(VolumeType::Gp2, VolumeType::St1, 100).unwrap_err(), VolumeLimitError::SizeOutOfRange { min: 125, max: 16384, got: 100 });
+        assert_eq!(validate_type_change(VolumeType::Gp2, VolumeType::Standard, 2000).unwrap_err(), VolumeLimitError::SizeOutOfRange { min: 1, max: 1024, got: 2000 });
+        assert!(validate_type_change(VolumeType::St1, VolumeType::Gp2, 100).is_err());
+    }
+
+    #[test]
+    fn test_utilization() {
+        let limit = calculate_gp2_limits(1000).unwrap();
+        let (iops, throughput) = utilization(&limit, 2400, 300);
+        assert!((iops - 0.8).abs() < 1e-9);
+        assert!((throughput - 1.2).abs() < 1e-9);
+        let st1 = calculate_st1_limits(1024).unwrap();
+        assert_eq!(utilization(&st1, 0, 20), (0.0, 0.5));
+        assert_eq!(utilization(&st1, 10, 0).0, f64::INFINITY);
+    }
+
+    #[test]
+    fn test_gp3_equivalent_of_small_gp2() {
+        assert_eq!(gp3_equivalent_of_gp2(100).unwrap(), (3000, 125));
+        for size in 1..=6 {
+            assert_eq!(gp3_equivalent_of_gp2(size).unwrap(), (3000, 125));
+        }
+        assert!(gp3_equivalent_of_gp2(0).is_err());
+    }
+
+    #[test]
+    fn test_gp3_equivalent_of_large_gp2() {
+        assert_eq!(gp3_equivalent_of_gp2(1500).unwrap(), (4500, 250));
+        assert_eq!(gp3_equivalent_of_gp2(6000).unwrap(), (16000, 250));
+    }
+
+    #[test]
+    fn test_limits_within_max_constants() {
+        for size in (1..=16384).step_by(97) {
+            let limit = calculate_gp2_limits(size).unwrap();
+            assert!(limit.iops <= GP2_MAX_IOPS && limit.speed <= GP2_MAX_THROUGHPUT && limit.burst_speed <= GP2_MAX_THROUGHPUT);
+            let limit = gp3_max_limit(size).unwrap();
+            assert!(limit.iops <= GP3_MAX_IOPS && limit.speed <= GP3_MAX_THROUGHPUT);
+            if let Ok(limit) = max_capability(VolumeType::Io1, size) {
+                assert!(limit.iops <= IO1_MAX_IOPS && limit.speed <= IO1_MAX_THROUGHPUT);
+            }
+            if let Ok(limit) = max_capability(VolumeType::Io2, size) {
+                assert!(limit.iops <= IO2_MAX_IOPS && limit.speed <= IO2_MAX_THROUGHPUT);
+            }
+            if let Ok(limit) = calculate_st1_limits(size) {
+                assert!(limit.speed <= ST1_MAX_THROUGHPUT && limit.burst_speed <= ST1_MAX_THROUGHPUT);
+            }
+            if let Ok(limit) = calculate_sc1_limits(size) {
+                assert!(limit.speed <= SC1_MAX_THROUGHPUT && limit.burst_speed <= SC1_MAX_BURST_THROUGHPUT);
+            }
+        }
+        let limit = calculate_io2_block_express_limits(IO2_BLOCK_EXPRESS_MAX_IOPS, None).unwrap();
+        assert!(limit.speed <= IO2_BLOCK_EXPRESS_MAX_THROUGHPUT);
+        assert_eq!(gp2_throughput_cap_size(), min_gp2_size_for_throughput(GP2_MAX_THROUGHPUT).unwrap());
+    }
+
+    #[test]
+    fn test_limit_ordering() {
+        let gp2 = calculate_gp2_limits(100).unwrap();                      // 300 IOPS, 75MiB/s baseline, bursts to 3000 IOPS
+        let gp3 = calculate_gp3_limits(100, None, None).unwrap();          // 3000 IOPS, 125MiB/s
+        let gp3_fast = calculate_gp3_limits(100, None, Some(500)).unwrap(); // 3000 IOPS, 500MiB/s
+        let io2 = calculate_io2_limits(100, 20000).unwrap();               // 20000 IOPS
+        let st1 = calculate_st1_limits(1000).unwrap();                     // No IOPS, throughput only
+        let mut limits = vec![io2.clone(), gp3_fast.clone(), st1.clone(), gp3.clone(), gp2.clone()];
+        limits.sort();
+        assert_eq!(limits, vec![st1, gp2, gp3, gp3_fast, io2.clone()]);
+        assert_eq!(limits.iter().max(), Some(&io2));
+    }
+
+    #[test]
+    fn test_min_io_iops_for_throughput() {
+        assert_eq!(min_io_iops_for_throughput(250), Ok(1000));
+        assert_eq!(min_io_iops_for_throughput(500), Ok(2000));
+        assert_eq!(min_io_iops_for_throughput(800), Ok(51200));
+        assert_eq!(min_io_iops_for_throughput(0), Ok(100));
+        assert_eq!(min_io_iops_for_throughput(1001), Err(VolumeLimitError::ThroughputTargetUnreachable { max: 1000, target: 1001 }));
+
+        for target in [250, 500, 501, 800, 1000] {
+            let iops = min_io_iops_for_throughput(target).unwrap();
+            assert!(io_limits(iops).unwrap().speed >= target);
+            assert!(iops == 100 || iops == 32000 || io_limits(iops - 1).unwrap().speed < target);
+        }
+    }
+
+    #[test]
+    fn test_gp3_min_size_for_iops() {
+        assert_eq!(gp3_min_size_for_iops(3000), Ok(6));
+        assert_eq!(gp3_min_size_for_iops(16000), Ok(32));
+        assert_eq!(gp3_min_size_for_iops(3001), Ok(7));
+        assert_eq!(gp3_min_size_for_iops(2999), Err(VolumeLimitError::IopsBelowBaseline { baseline: 3000, got: 2999 }));
+        assert_eq!(gp3_min_size_for_iops(16001), Err(VolumeLimitError::IopsOutOfRange { min: 3000, max: 16000, got: 16001 }));
+
+        for iops in [3000, 3001, 9999, 16000] {
+            let size = gp3_min_size_for_iops(iops).unwrap();
+            assert!(calculate_gp3_limits(size, Some(iops), None).is_ok());
+            assert_eq!(calculate_gp3_limits(size - 1, Some(iops), None), Err(VolumeLimitError::IopsToSizeRatioExceeded { max_ratio: 500, iops, size: size - 1 }));
+        }
+    }
+
+    #[test]
+    fn test_all_limits() {
+        let limits = all_limits(500);
+        assert_eq!(limits.len(), VolumeType::ALL.len());
+        for (volume_type, limit) in &limits {
+            assert!(limit.is_ok(), "{volume_type} failed at 500GiB");
+        }
+        assert_eq!(limits[1], (VolumeType::Gp3, calculate_gp3_limits(500, None, None)));
+
+        for (volume_type, limit) in all_limits(50) {
+            match volume_type {
+                VolumeType::St1 | VolumeType::Sc1 => assert_eq!(limit, Err(VolumeLimitError::SizeOutOfRange { min: 125, max: 16384, got: 50 })),
+                _ => assert!(limit.is_ok()),
+            }
+        }
+    }
+
+    #[test]
+    fn test_ratio_to() {
+        let gp2 = calculate_gp2_limits(1000).unwrap();                              // 3000 IOPS, 250MiB/s
+        let gp3 = calculate_gp3_limits(1000, Some(4500), Some(500)).unwrap();
+        assert_eq!(gp3.ratio_to(&gp2), (1.5, 2.0));
+        assert_eq!(gp2.ratio_to(&gp3), (3000.0 / 4500.0, 0.5));
+        assert_eq!(gp2.ratio_to(&gp2), (1.0, 1.0));
+
+        let st1 = calculate_st1_limits(1000).unwrap();                              // No IOPS
+        assert_eq!(gp2.ratio_to(&st1).0, f64::INFINITY);
+        assert_eq!(st1.ratio_to(&st1).0, 0.0);
+    }
+
+    #[test]
+    fn test_formula_params_gp2() {
+        let params = formula_params(VolumeType::Gp2);
+        assert_eq!((params.iops_per_gib, params.iops_floor, params.iops_ceiling), (3, 100, 16000));
+        assert_eq!(params.throughput_ceiling, 250);
+        assert_eq!(params.throughput_divisor, Some(4));
+
+        for size in [1, 34, 500, 5333, 10000] {
+            let limit = calculate_gp2_limits(size).unwrap();
+            assert_eq!(limit.iops, (params.iops_per_gib * size).clamp(params.iops_floor, params.iops_ceiling));
+        }
+        assert!(calculate_gp2_limits(params.min_size_gb - 1).is_err());
+        assert!(calculate_gp2_limits(params.max_size_gb + 1).is_err());
+        assert_eq!(calculate_gp2_limits(params.max_size_gb).unwrap().speed, params.throughput_ceiling);
+    }
+
+    #[test]
+    fn test_formula_params_size_range() {
+        for volume_type in VolumeType::ALL {
+            let params = formula_params(volume_type);
+            assert!(default_provisioned_limits(volume_type, params.min_size_gb).is_ok(), "{volume_type}");
+            assert!(default_provisioned_limits(volume_type, params.max_size_gb).is_ok(), "{volume_type}");
+            if volume_type != VolumeType::Io1 {                 // The io1 size range isn't enforced
+                assert!(default_provisioned_limits(volume_type, params.min_size_gb - 1).is_err(), "{volume_type}");
+                assert!(default_provisioned_limits(volume_type, params.max_size_gb + 1).is_err(), "{volume_type}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_formula_params_ceilings() {
+        for volume_type in VolumeType::ALL {
+            let params = formula_params(volume_type);
+            for size in [params.min_size_gb, params.max_size_gb] {
+                let limit = max_capability(volume_type, size).unwrap();
+                assert!(limit.iops <= params.iops_ceiling && limit.speed <= params.throughput_ceiling, "{volume_type}");
+                assert!(limit.burst_iops <= params.burst_iops_ceiling && limit.burst_speed <= params.burst_throughput_ceiling, "{volume_type}");
+            }
+        }
+        let standard = formula_params(VolumeType::Standard);
+        assert_eq!(
+            calculate_standard_limits(100).unwrap(),
+            Limit { iops: standard.iops_ceiling, speed: standard.throughput_ceiling, burst_iops: standard.burst_iops_ceiling, burst_speed: standard.burst_throughput_ceiling }
+        );
+    }
+
+    #[test]
+    fn test_formula_params_hdd_throughput() {
+        for (volume_type, size) in [(VolumeType::St1, 2048), (VolumeType::Sc1, 2048)] {
+            let params = formula_params(volume_type);
+            let limit = calculate(volume_type, size, None, None).unwrap();
+            assert_eq!(limit.speed, params.throughput_per_tib * size / 1024);
+            assert_eq!(limit.burst_speed, params.burst_throughput_per_tib * size / 1024);
+        }
+    }
 }
@@ -0,0 +1,87 @@
+use std::env;
+use std::process::ExitCode;
+
+use aws_volume_limit_calculator::{calculate, VolumeType};
+
+const USAGE: &str = "Usage: aws-volume-limit --type <gp2|gp3|io1|io2|st1|sc1|standard> --size <GiB> [--iops <IOPS>] [--throughput <MiB/s>] [--format <human|json>]";
+
+enum Format {
+    Human,
+    Json,
+}
+
+struct Args {
+    volume_type: VolumeType,
+    size_gb: u32,
+    iops: Option<u32>,
+    throughput: Option<u32>,
+    format: Format,
+}
+
+fn parse_number(flag: &str, value: &str) -> Result<u32, String> {
+    value.parse().map_err(|_| format!("Invalid value for {}: {}", flag, value))
+}
+
+// `None` when help was requested. Help is only recognized where a flag is expected, not as the value of another flag.
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Option<Args>, String> {
+    let mut volume_type = None;
+    let mut size_gb = None;
+    let mut iops = None;
+    let mut throughput = None;
+    let mut format = Format::Human;
+
+    while let Some(flag) = args.next() {
+        if flag == "--help" || flag == "-h" {
+            return Ok(None);
+        }
+        let value = args.next().ok_or_else(|| format!("Missing value for {}", flag))?;
+        match flag.as_str() {
+            "--type" => volume_type = Some(value.parse::<VolumeType>().map_err(|_| format!("Unknown volume type: {}", value))?),
+            "--size" => size_gb = Some(parse_number(&flag, &value)?),
+            "--iops" => iops = Some(parse_number(&flag, &value)?),
+            "--throughput" => throughput = Some(parse_number(&flag, &value)?),
+            "--format" => format = match value.as_str() {
+                "human" => Format::Human,
+                "json" => Format::Json,
+                _ => return Err(format!("Unknown format: {}", value)),
+            },
+            _ => return Err(format!("Unknown argument: {}", flag)),
+        }
+    }
+
+    Ok(Some(Args {
+        volume_type: volume_type.ok_or("Missing --type")?,
+        size_gb: size_gb.ok_or("Missing --size")?,
+        iops,
+        throughput,
+        format,
+    }))
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args(env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return ExitCode::SUCCESS;
+        }
+        Err(error) => {
+            eprintln!("{}\n{}", error, USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    match calculate(args.volume_type, args.size_gb, args.iops, args.throughput) {
+        Ok(limit) => {
+            match args.format {
+                Format::Human => println!("{}", limit),
+                Format::Json => println!("{}", limit.to_json()),
+            }
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            eprintln!("{}", error);
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -0,0 +1,66 @@
+use crate::{calculate, Limit, VolumeLimitError, VolumeType};
+
+/// Receives every limit computed through `calculate_observed`, e.g. to log or meter queries.
+pub trait LimitObserver {
+    fn on_calculated(&self, volume_type: VolumeType, input_size: u32, result: &Limit);
+}
+
+/// Observer that ignores every calculation.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoopObserver;
+
+impl LimitObserver for NoopObserver {
+    fn on_calculated(&self, _volume_type: VolumeType, _input_size: u32, _result: &Limit) {}
+}
+
+/// Same as `calculate`, reporting the result to `observer`. Failed calculations are not reported.
+pub fn calculate_observed(
+    observer: &dyn LimitObserver,
+    volume_type: VolumeType,
+    volume_size_gb: u32,
+    provisioned_iops: Option<u32>,
+    provisioned_throughput: Option<u32>,
+) -> Result<Limit, VolumeLimitError> {
+    let limit = calculate(volume_type, volume_size_gb, provisioned_iops, provisioned_throughput)?;
+    observer.on_calculated(volume_type, volume_size_gb, &limit);
+    Ok(limit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::cell::Cell;
+
+    #[derive(Default)]
+    struct CountingObserver {
+        calls: Cell<u32>,
+        last_size: Cell<u32>,
+    }
+
+    impl LimitObserver for CountingObserver {
+        fn on_calculated(&self, _volume_type: VolumeType, input_size: u32, _result: &Limit) {
+            self.calls.set(self.calls.get() + 1);
+            self.last_size.set(input_size);
+        }
+    }
+
+    #[test]
+    fn test_calculate_observed() {
+        let observer = CountingObserver::default();
+        let limit = calculate_observed(&observer, VolumeType::Gp2, 100, None, None).unwrap();
+        assert_eq!(limit, calculate(VolumeType::Gp2, 100, None, None).unwrap());
+        assert_eq!(observer.calls.get(), 1);
+
+        calculate_observed(&observer, VolumeType::Io2, 200, Some(5000), None).unwrap();
+        assert_eq!(observer.calls.get(), 2);
+        assert_eq!(observer.last_size.get(), 200);
+
+        assert!(calculate_observed(&observer, VolumeType::Io1, 100, None, None).is_err());
+        assert_eq!(observer.calls.get(), 2);
+    }
+
+    #[test]
+    fn test_noop_observer() {
+        assert_eq!(calculate_observed(&NoopObserver, VolumeType::St1, 500, None, None), calculate(VolumeType::St1, 500, None, None));
+    }
+}
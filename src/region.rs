@@ -0,0 +1,63 @@
+use crate::{
+    Limit, VolumeType, GP2_BURST_IOPS, GP2_MAX_IOPS, GP2_MAX_THROUGHPUT, GP3_MAX_IOPS, GP3_MAX_THROUGHPUT, IO1_MAX_IOPS,
+    IO1_MAX_THROUGHPUT, IO2_BLOCK_EXPRESS_MAX_IOPS, IO2_BLOCK_EXPRESS_MAX_THROUGHPUT, IO2_MAX_IOPS, IO2_MAX_THROUGHPUT,
+    SC1_MAX_BURST_THROUGHPUT, SC1_MAX_THROUGHPUT, STANDARD_BURST_IOPS, STANDARD_BURST_THROUGHPUT, STANDARD_IOPS,
+    STANDARD_THROUGHPUT, ST1_MAX_THROUGHPUT,
+};
+
+const fn ceiling(iops: u32, speed: u32, burst_iops: u32, burst_speed: u32) -> Limit {
+    Limit { iops, speed, burst_iops, burst_speed }
+}
+
+// Per volume type ceilings in commercial regions, used for every region without an override below.
+const DEFAULT_MAX_LIMITS: &[(VolumeType, Limit)] = &[
+    (VolumeType::Gp2, ceiling(GP2_MAX_IOPS, GP2_MAX_THROUGHPUT, GP2_BURST_IOPS, GP2_MAX_THROUGHPUT)),
+    (VolumeType::Gp3, ceiling(GP3_MAX_IOPS, GP3_MAX_THROUGHPUT, 0, 0)),
+    (VolumeType::Io1, ceiling(IO1_MAX_IOPS, IO1_MAX_THROUGHPUT, 0, 0)),
+    (VolumeType::Io2, ceiling(IO2_BLOCK_EXPRESS_MAX_IOPS, IO2_BLOCK_EXPRESS_MAX_THROUGHPUT, 0, 0)),
+    (VolumeType::St1, ceiling(0, ST1_MAX_THROUGHPUT, 0, ST1_MAX_THROUGHPUT)),
+    (VolumeType::Sc1, ceiling(0, SC1_MAX_THROUGHPUT, 0, SC1_MAX_BURST_THROUGHPUT)),
+    (VolumeType::Standard, ceiling(STANDARD_IOPS, STANDARD_THROUGHPUT, STANDARD_BURST_IOPS, STANDARD_BURST_THROUGHPUT)),
+];
+
+// Regions where a volume type is capped below the default ceiling.
+const REGIONAL_MAX_LIMITS: &[(&str, VolumeType, Limit)] = &[
+    ("cn-north-1", VolumeType::Io2, ceiling(IO2_MAX_IOPS, IO2_MAX_THROUGHPUT, 0, 0)),    // io2 Block Express is not available
+    ("cn-northwest-1", VolumeType::Io2, ceiling(IO2_MAX_IOPS, IO2_MAX_THROUGHPUT, 0, 0)),
+];
+
+/// Highest IOPS and throughput a volume type supports in `region`.
+/// Regions without a known exception, including unknown ones, get the commercial region ceilings.
+pub fn max_limits(volume_type: VolumeType, region: &str) -> Limit {
+    if let Some((_, _, limit)) = REGIONAL_MAX_LIMITS.iter().find(|(name, vt, _)| *name == region && *vt == volume_type) {
+        return limit.clone();
+    }
+    DEFAULT_MAX_LIMITS.iter()
+        .find(|(vt, _)| *vt == volume_type)
+        .map(|(_, limit)| limit.clone())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_default_io2_ceiling() {
+        assert_eq!(max_limits(VolumeType::Io2, "us-east-1"), Limit { iops: 256000, speed: 4000, burst_iops: 0, burst_speed: 0 });
+    }
+
+    #[test]
+    fn test_regional_override() {
+        assert_eq!(max_limits(VolumeType::Io2, "cn-north-1"), Limit { iops: 64000, speed: 1000, burst_iops: 0, burst_speed: 0 });
+        assert_eq!(max_limits(VolumeType::Gp3, "cn-north-1"), max_limits(VolumeType::Gp3, "us-east-1"));
+    }
+
+    #[test]
+    fn test_unknown_region_uses_defaults() {
+        for volume_type in VolumeType::ALL {
+            assert_eq!(max_limits(volume_type, "xx-nowhere-9"), max_limits(volume_type, "us-east-1"));
+        }
+        assert_eq!(max_limits(VolumeType::Gp2, "xx-nowhere-9"), Limit { iops: 16000, speed: 250, burst_iops: 3000, burst_speed: 250 });
+    }
+}
@@ -0,0 +1,115 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt::Write;
+
+use crate::Limit;
+
+// Quotes the field when it contains a separator, a quote or a line break, as RFC 4180 requires.
+fn push_csv_field(csv: &mut String, field: &str) {
+    if field.contains([',', '"', '\r', '\n']) {
+        csv.push('"');
+        csv.push_str(&field.replace('"', "\"\""));
+        csv.push('"');
+    } else {
+        csv.push_str(field);
+    }
+}
+
+/// CSV report with a header line and one row per named limit.
+pub fn limits_to_csv(rows: &[(String, Limit)]) -> String {
+    let mut csv = String::from("name,iops,speed,burst_iops,burst_speed\n");
+    for (name, limit) in rows {
+        push_csv_field(&mut csv, name);
+        // Writing into a String can't fail.
+        let _ = writeln!(csv, ",{},{},{},{}", limit.iops, limit.speed, limit.burst_iops, limit.burst_speed);
+    }
+    csv
+}
+
+const TABLE_HEADER: [&str; 5] = ["Name", "IOPS", "MiB/s", "Burst IOPS", "Burst MiB/s"];
+
+/// Aligned plain text table with one row per named limit, the name is left-aligned and numbers right-aligned.
+pub fn format_table(rows: &[(String, Limit)]) -> String {
+    let cells: Vec<[String; 5]> = rows.iter()
+        .map(|(name, limit)| [
+            name.clone(),
+            limit.iops.to_string(),
+            limit.speed.to_string(),
+            limit.burst_iops.to_string(),
+            limit.burst_speed.to_string(),
+        ])
+        .collect();
+    let mut widths = TABLE_HEADER.map(|title| title.chars().count());
+    for row in &cells {
+        for (width, cell) in widths.iter_mut().zip(row) {
+            *width = (*width).max(cell.chars().count());
+        }
+    }
+
+    let mut table = String::new();
+    push_table_row(&mut table, &TABLE_HEADER, &widths);
+    let rule = widths.map(|width| "-".repeat(width));
+    push_table_row(&mut table, &rule.each_ref().map(String::as_str), &widths);
+    for row in &cells {
+        push_table_row(&mut table, &row.each_ref().map(String::as_str), &widths);
+    }
+    table
+}
+
+fn push_table_row(table: &mut String, row: &[&str; 5], widths: &[usize; 5]) {
+    // Writing into a String can't fail.
+    let _ = write!(table, "{:<width$}", row[0], width = widths[0]);
+    for (cell, width) in row.iter().zip(widths).skip(1) {
+        let _ = write!(table, "  {:>width$}", cell, width = *width);
+    }
+    table.push('\n');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{calculate_gp2_limits, calculate_gp3_limits};
+
+    #[test]
+    fn test_csv() {
+        let rows = [
+            (String::from("vol-1"), calculate_gp2_limits(20).unwrap()),
+            (String::from("vol-2"), calculate_gp3_limits(500, Some(6000), None).unwrap()),
+        ];
+        assert_eq!(limits_to_csv(&rows), "name,iops,speed,burst_iops,burst_speed\nvol-1,100,25,3000,128\nvol-2,6000,125,0,0\n");
+    }
+
+    #[test]
+    fn test_csv_quoting() {
+        let rows = [
+            (String::from("db, primary"), calculate_gp2_limits(1500).unwrap()),
+            (String::from("the \"logs\" volume"), calculate_gp2_limits(1500).unwrap()),
+        ];
+        assert_eq!(limits_to_csv(&rows), "name,iops,speed,burst_iops,burst_speed\n\"db, primary\",4500,250,0,0\n\"the \"\"logs\"\" volume\",4500,250,0,0\n");
+    }
+
+    #[test]
+    fn test_csv_empty() {
+        assert_eq!(limits_to_csv(&[]), "name,iops,speed,burst_iops,burst_speed\n");
+    }
+
+    #[test]
+    fn test_table_alignment() {
+        let rows = [
+            (String::from("vol-1"), calculate_gp2_limits(20).unwrap()),
+            (String::from("database"), calculate_gp3_limits(1000, Some(16000), Some(1000)).unwrap()),
+        ];
+        assert_eq!(
+            format_table(&rows),
+            "Name       IOPS  MiB/s  Burst IOPS  Burst MiB/s\n\
+             --------  -----  -----  ----------  -----------\n\
+             vol-1       100     25        3000          128\n\
+             database  16000   1000           0            0\n"
+        );
+    }
+
+    #[test]
+    fn test_table_empty() {
+        assert_eq!(format_table(&[]), "Name  IOPS  MiB/s  Burst IOPS  Burst MiB/s\n----  ----  -----  ----------  -----------\n");
+    }
+}
@@ -0,0 +1,45 @@
+#[cfg(feature = "wasm")]
+use alloc::string::ToString;
+#[cfg(feature = "wasm")]
+use wasm_bindgen::prelude::*;
+
+use crate::{calculate_gp3_limits, non_negative, Limit, VolumeLimitError};
+
+// JavaScript callers pass -1 for values they don't provide, any other negative value is rejected.
+fn optional_parameter(name: &'static str, value: i32) -> Result<Option<u32>, VolumeLimitError> {
+    if value == -1 {
+        return Ok(None);
+    }
+    non_negative(name, value).map(Some)
+}
+
+fn calculate_gp3_from_js(size: u32, iops: i32, throughput: i32) -> Result<Limit, VolumeLimitError> {
+    calculate_gp3_limits(size, optional_parameter("iops", iops)?, optional_parameter("throughput", throughput)?)
+}
+
+/// `calculate_gp3_limits` for JavaScript, -1 means the IOPS or throughput is not provided.
+/// Returns the `Limit` as an object, errors are thrown with their message.
+#[cfg(feature = "wasm")]
+#[wasm_bindgen]
+pub fn calculate_gp3_wasm(size: u32, iops: i32, throughput: i32) -> Result<JsValue, JsValue> {
+    let limit = calculate_gp3_from_js(size, iops, throughput).map_err(|error| JsValue::from_str(&error.to_string()))?;
+    serde_wasm_bindgen::to_value(&limit).map_err(JsValue::from)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_parameters() {
+        assert_eq!(calculate_gp3_from_js(500, -1, -1).unwrap(), calculate_gp3_limits(500, None, None).unwrap());
+        assert_eq!(calculate_gp3_from_js(500, 6000, -1).unwrap(), calculate_gp3_limits(500, Some(6000), None).unwrap());
+        assert_eq!(calculate_gp3_from_js(500, -1, 250).unwrap(), calculate_gp3_limits(500, None, Some(250)).unwrap());
+    }
+
+    #[test]
+    fn test_negative_parameters() {
+        assert_eq!(calculate_gp3_from_js(500, -2, -1).unwrap_err(), VolumeLimitError::NegativeParameter { name: "iops", got: -2 });
+        assert_eq!(calculate_gp3_from_js(500, -1, -250).unwrap_err(), VolumeLimitError::NegativeParameter { name: "throughput", got: -250 });
+    }
+}
@@ -0,0 +1,49 @@
+use std::process::{Command, Output};
+
+fn run(args: &[&str]) -> Output {
+    Command::new(env!("CARGO_BIN_EXE_aws-volume-limit")).args(args).output().unwrap()
+}
+
+#[test]
+fn test_human_output() {
+    let output = run(&["--type", "gp3", "--size", "500", "--iops", "6000", "--throughput", "250"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "6000 IOPS, 250 MiB/s throughput\n");
+}
+
+#[test]
+fn test_json_output() {
+    let output = run(&["--type", "gp2", "--size", "20", "--format", "json"]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8(output.stdout).unwrap(), "{\"iops\":100,\"speed\":25,\"burst_iops\":3000,\"burst_speed\":128}\n");
+}
+
+#[test]
+fn test_invalid_input() {
+    let output = run(&["--type", "gp3", "--size", "0"]);
+    assert_eq!(output.status.code(), Some(1));
+    assert!(output.stdout.is_empty());
+    assert_eq!(String::from_utf8(output.stderr).unwrap(), "Volume size can not be less than 1GiB or greater than 16384GiB, got 0GiB.\n");
+}
+
+#[test]
+fn test_invalid_arguments() {
+    let output = run(&["--type", "gp4", "--size", "500"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8(output.stderr).unwrap().starts_with("Unknown volume type: gp4"));
+
+    let output = run(&["--type", "gp3"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8(output.stderr).unwrap().starts_with("Missing --size"));
+}
+
+#[test]
+fn test_help() {
+    let output = run(&["--type", "gp3", "--help"]);
+    assert!(output.status.success());
+    assert!(String::from_utf8(output.stdout).unwrap().starts_with("Usage: aws-volume-limit"));
+
+    let output = run(&["--size", "--help"]);
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8(output.stderr).unwrap().starts_with("Invalid value for --size: --help"));
+}
@@ -0,0 +1,11 @@
+// Compiles against the library from a crate without the standard library,
+// run with `--features no_std` to also build the library itself as no_std.
+#![no_std]
+
+use aws_volume_limit_calculator::{calculate, calculate_gp2_limits, Limit, VolumeLimitError, VolumeType};
+
+#[test]
+fn test_calculate_without_std() {
+    assert_eq!(calculate_gp2_limits(20), Ok(Limit { iops: 100, speed: 25, burst_iops: 3000, burst_speed: 128 }));
+    assert_eq!(calculate(VolumeType::Gp3, 0, None, None), Err(VolumeLimitError::SizeOutOfRange { min: 1, max: 16384, got: 0 }));
+}